@@ -0,0 +1,143 @@
+use crate::chunk::{Asconf, AsconfAck, AsconfOp, AsconfRequest};
+use std::net::IpAddr;
+
+/// Chunk types listed in the Supported Extensions param when ASCONF is supported
+pub const ASCONF_CHUNK_TYPES: [u8; 2] = [0xc1, 0x80];
+
+/// Outcome of an ASCONF request: the request along with
+/// the cause code it failed with, `None` if it succeeded
+pub type AsconfResult = (AsconfRequest, Option<u16>);
+
+/// Numbers the ASCONFs sent to the peer and matches the
+/// ASCONF-ACKs that come back (RFC 5061 section 5.1).
+/// Only one ASCONF can be outstanding at a time.
+pub struct AsconfSender {
+    // serial no of the next ASCONF
+    serial_no: u32,
+    next_correlation_id: u32,
+    // ASCONF waiting to be acked
+    outstanding: Option<Asconf>,
+    // results of the last ASCONF that got acked
+    results: Option<Vec<AsconfResult>>,
+}
+
+impl AsconfSender {
+    /// Creates a sender whose serial nos start from our initial TSN
+    pub fn new(initial_tsn: u32) -> Self {
+        Self {
+            serial_no: initial_tsn,
+            next_correlation_id: 0,
+            outstanding: None,
+            results: None,
+        }
+    }
+
+    /// Creates an ASCONF carrying the given operations, sent from `addr`.
+    /// Returns `None` if the previous ASCONF hasn't been acked yet.
+    pub fn send(&mut self, addr: IpAddr, ops: &[(AsconfOp, IpAddr)]) -> Option<Asconf> {
+        if self.outstanding.is_some() {
+            return None;
+        }
+
+        let requests = ops
+            .iter()
+            .map(|&(op, addr)| {
+                self.next_correlation_id = self.next_correlation_id.wrapping_add(1);
+                AsconfRequest {
+                    op,
+                    correlation_id: self.next_correlation_id,
+                    addr,
+                }
+            })
+            .collect();
+        let asconf = Asconf::new(self.serial_no, addr, requests);
+        self.serial_no = self.serial_no.wrapping_add(1);
+        self.outstanding = Some(asconf.clone());
+        self.results = None;
+        Some(asconf)
+    }
+
+    /// Returns the ASCONF waiting to be acked, to be retransmitted
+    pub fn outstanding(&self) -> Option<&Asconf> {
+        self.outstanding.as_ref()
+    }
+
+    /// Matches an ASCONF-ACK with the outstanding ASCONF.
+    /// Returns false if it doesn't ack the outstanding ASCONF.
+    pub fn on_ack(&mut self, ack: &AsconfAck) -> bool {
+        let asconf = match self.outstanding.as_ref() {
+            Some(asconf) if asconf.serial_no() == ack.serial_no() => asconf,
+            _ => return false,
+        };
+
+        // section 5.3: requests without a response succeeded
+        let results = asconf
+            .requests()
+            .iter()
+            .map(|request| {
+                let error = ack
+                    .responses()
+                    .iter()
+                    .find(|response| response.correlation_id == request.correlation_id)
+                    .and_then(|response| response.error);
+                (request.clone(), error)
+            })
+            .collect();
+        self.outstanding = None;
+        self.results = Some(results);
+        true
+    }
+
+    /// Takes the results of the last ASCONF that got acked
+    pub fn take_results(&mut self) -> Option<Vec<AsconfResult>> {
+        self.results.take()
+    }
+}
+
+/// What to do with an ASCONF recvd from the peer
+#[derive(Debug)]
+pub enum AsconfVerdict {
+    /// the ASCONF is the next one in sequence and must be processed
+    New,
+    /// the ASCONF was processed before and its ASCONF-ACK got lost
+    Retransmitted(AsconfAck),
+    /// the ASCONF is out of sequence and is silently discarded
+    Stale,
+}
+
+/// Processes the ASCONFs of the peer in serial no order (RFC 5061 section 5.2)
+pub struct AsconfReceiver {
+    // serial no of the last ASCONF processed
+    serial_no: u32,
+    // ASCONF-ACK of the last ASCONF processed
+    last_ack: Option<AsconfAck>,
+}
+
+impl AsconfReceiver {
+    /// Creates a receiver expecting the serial nos of the peer
+    /// to start from its initial TSN
+    pub fn new(peer_initial_tsn: u32) -> Self {
+        Self {
+            serial_no: peer_initial_tsn.wrapping_sub(1),
+            last_ack: None,
+        }
+    }
+
+    pub fn on_asconf(&self, asconf: &Asconf) -> AsconfVerdict {
+        if asconf.serial_no() == self.serial_no.wrapping_add(1) {
+            return AsconfVerdict::New;
+        }
+        match self.last_ack.as_ref() {
+            Some(ack) if asconf.serial_no() == self.serial_no => {
+                AsconfVerdict::Retransmitted(ack.clone())
+            }
+            _ => AsconfVerdict::Stale,
+        }
+    }
+
+    /// Remembers the ASCONF-ACK sent for the last ASCONF processed
+    pub fn on_processed(&mut self, ack: AsconfAck) {
+        self.serial_no = ack.serial_no();
+        self.last_ack = Some(ack);
+    }
+}
@@ -1,146 +1,303 @@
-use crate::chunk::{Abort, CookieAck, CookieEcho, Data, Init, InitAck, ParamType, Parameter, Sack};
+use crate::asconf::{AsconfReceiver, AsconfSender, AsconfVerdict, ASCONF_CHUNK_TYPES};
+use crate::auth::{Authenticator, AUTH_CHUNK_TYPE};
+use crate::chunk::{
+    padding, Abort, Asconf, AsconfAck, AsconfOp, AsconfRequest, AsconfResponse, Auth, Cause, Chunk,
+    ChunkType, CookieAck, CookieEcho, Cwr, Data, Ecne, Error, ForwardTsn, Heartbeat, HeartbeatAck,
+    IData, IForwardTsn, Init, InitAck, InvalidStreamId, NrSack, ParamType, Parameter, ReConfig,
+    ReconfigRequest, ReconfigResponse, ReconfigResult, Sack, UnrecognizedChunkType,
+    DELETE_LAST_ADDR_CAUSE, DELETE_SOURCE_ADDR_CAUSE, I_DATA_CHUNK_TYPE, I_FORWARD_TSN_CHUNK_TYPE,
+    NR_SACK_CHUNK_TYPE, UNRESOLVABLE_ADDR_CAUSE,
+};
+use crate::config::{AssociationConfig, SendOptions};
 use crate::cookie::Cookie;
+use crate::ecn::{EcnReceiver, EcnSender};
 use crate::error::SCTPError;
+use crate::heartbeat::HeartbeatInfo;
+use crate::notification::Notification;
 use crate::packet::Packet;
+use crate::path::{select_primary, Path, PathState, PathThresholds};
+use crate::pr::{forward_tsn, i_forward_tsn, AbandonedStats, Abandonment};
+use crate::reassembly::{InterleavedReassemblyQueue, Message, Reassembled, ReassemblyQueue};
+use crate::reconfig::{ReconfigReceiver, ReconfigSender, ReconfigVerdict, RECONFIG_CHUNK_TYPE};
+use crate::recv_buffer::{DataVerdict, RecvBuffer};
+use crate::reorder::ReorderQueue;
+use crate::rto::SrttReport;
+use crate::scheduler::Scheduler;
 use crate::stream::Stream;
-use herschel::pmtud::Pmtud;
+use crate::tsn::tsn_lte;
 use log::{debug, info};
 use rand::{rngs::ThreadRng, thread_rng, Rng};
+use std::collections::VecDeque;
 use std::convert::TryFrom;
-use std::net::SocketAddr;
-use std::{cmp, collections::VecDeque};
-use tokio::time::{sleep, timeout, Duration};
+use std::net::{IpAddr, SocketAddr};
+use tokio::time::{sleep, timeout, timeout_at, Duration, Instant};
 
-const RTO_INITIAL: u64 = 3;
-const RTO_MIN: u8 = 1;
-const RTO_MAX: u8 = 60;
 const MAX_BURST: u8 = 4;
-// const  RTO_ALPHA = 1/8
-// const  RTO_BETA = 1/4
 const VALID_COOKIE_LIFE: u8 = 60;
 const ASSOCIATION_MAX_RETRANS: u8 = 10;
-const PATH_MAX_RETRANS: u8 = 5;
 const MAX_INIT_RETRANSMITS: u8 = 8;
-const HB_INTERVAL: u8 = 30;
 const HB_MAX_BURST: u8 = 1;
+const IPV4_HEADER_SIZE: usize = 20;
+const IPV6_HEADER_SIZE: usize = 40;
+// we run over udp
+const UDP_HEADER_SIZE: usize = 8;
+const COMMON_HEADER_SIZE: usize = 12;
+const DATA_CHUNK_HEADER_SIZE: usize = 16;
+const I_DATA_CHUNK_HEADER_SIZE: usize = 20;
+
+/// Identifies a DATA chunk within its msg when it is sent as I-DATA (RFC 8260 section 2.1)
+#[derive(Clone, Copy)]
+struct Fragment {
+    message_id: u32,
+    // fragment seq no
+    fsn: u32,
+}
+
+/// Identifies a msg among the unacked chunks by its stream, whether it is unordered
+/// and its message id, which is the stream seq no of an ordered msg sent as DATA
+#[derive(Clone, Copy, PartialEq)]
+struct MessageKey {
+    stream_id: u16,
+    unordered: bool,
+    message_id: u32,
+}
+
+impl MessageKey {
+    fn new(chunk: &Data, fragment: Fragment) -> Self {
+        Self {
+            stream_id: chunk.stream_id(),
+            unordered: chunk.is_unordered(),
+            message_id: fragment.message_id,
+        }
+    }
+}
+
+/// A DATA chunk waiting to be sent to the peer
+struct QueuedChunk {
+    chunk: Data,
+    fragment: Fragment,
+    abandonment: Abandonment,
+}
+
+/// A DATA chunk that has been sent to the peer but not acked yet
+struct OutstandingChunk {
+    chunk: Data,
+    fragment: Fragment,
+    sent_at: Instant,
+    // 6.3.1 C5) Karn's algorithm: rtt measurements must not be made
+    // using chunks that have been retransmitted
+    retransmissions: u32,
+    // index of the path the chunk was last sent on
+    path: usize,
+    abandonment: Abandonment,
+    // RFC 3758 section 3.5: abandoned chunks are never retransmitted and wait
+    // for the peer to ack the FORWARD TSN that skips them
+    abandoned: bool,
+}
 
 /// An SCTP Association
 pub struct Association {
     id: String,
-    local_addr: SocketAddr,
-    remote_addr: Option<SocketAddr>,
+    local_addrs: Vec<SocketAddr>,
     stream: Stream,
-    pub msg_queue: VecDeque<Packet>,
+    pub msg_queue: VecDeque<Message>,
     rng: ThreadRng,
     init_tag: u32,
-    stream_id: u16,
-    stream_seq_no: u16,
+    peer_ver_tag: u32,
+    // next stream seq no of every outbound stream. with I-DATA this is
+    // the next message id of the ordered msgs and can go beyond 2^16 - 1
+    outbound_ssns: Vec<u32>,
+    // next message id of the unordered msgs of every outbound stream, only used with I-DATA
+    unordered_mids: Vec<u32>,
     max_retries: u8,
     max_init_retries: u8,
-    rto: u64,
     tsn: u32,
-    largest_tsn: u32,
+    recv_buffer: RecvBuffer,
+    reassembly: ReassemblyQueue,
+    interleaved_reassembly: InterleavedReassemblyQueue,
+    inbound_streams: ReorderQueue,
     remote_rwnd: u32,
-    mtu: Option<u16>,
-    cwnd: Option<u16>,
-    ssthresh: Option<u16>,
+    // queued chunks get their TSN when the scheduler picks them for transmission
+    send_queue: VecDeque<QueuedChunk>,
+    scheduler: Scheduler,
+    outstanding: VecDeque<OutstandingChunk>,
+    // every transport address of the peer
+    paths: Vec<Path>,
+    // index of the path new DATA is sent on
+    primary: usize,
+    // index of the primary path set by the ULP
+    preferred_primary: usize,
+    // consecutive T3-rtx expirations and unacked heartbeats on all paths (section 8.1)
+    error_count: u32,
+    failed: bool,
+    notifications: VecDeque<Notification>,
+    // chunk types the peer listed in its Supported Extensions param
+    peer_extensions: Vec<u8>,
+    // true if the peer sent the Forward-TSN-Supported param (RFC 3758 section 3.3.1)
+    peer_forward_tsn: bool,
+    // adaptation code point the peer sent in its INIT/INIT ACK
+    peer_adaptation_code_point: Option<u32>,
+    // true if both endpoints support I-DATA (RFC 8260 section 2.2)
+    interleaving: bool,
+    // true if both endpoints support NR-SACK, which is then sent instead of SACK
+    nr_sack: bool,
+    // true if both endpoints are ECN capable and our datagrams are marked ECT(0)
+    ecn: bool,
+    // true if we announced an alternate error detection method,
+    // so packets with a zero checksum are accepted (RFC 9653 section 5.3)
+    accept_zero_checksum: bool,
+    // true if the peer announced the error detection method we use,
+    // so packets are sent with a zero checksum (RFC 9653 section 5.2)
+    send_zero_checksum: bool,
+    ecn_sender: EcnSender,
+    ecn_receiver: EcnReceiver,
+    abandoned: AbandonedStats,
+    asconf: AsconfSender,
+    peer_asconf: AsconfReceiver,
+    reconfig: ReconfigSender,
+    peer_reconfig: ReconfigReceiver,
+    auth: Authenticator,
+    config: AssociationConfig,
 }
 
 impl Association {
     /// Creates a new sender endpoint
     pub async fn new_sender(
-        local_addr: impl AsRef<str>,
-        remote_addr: impl AsRef<str>,
+        local_addrs: Vec<SocketAddr>,
+        remote_addrs: Vec<SocketAddr>,
+        config: AssociationConfig,
     ) -> Result<Self, SCTPError> {
-        let local_sockaddr: SocketAddr = local_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidLocalAddress)?;
-        let remote_sockaddr: SocketAddr = remote_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidRemoteAddress)?;
-
-        let (mtu, cwnd, ssthresh) = match Pmtud::new(
-            local_addr.as_ref().parse().unwrap(),
-            remote_addr.as_ref().parse().unwrap(),
-        ) {
-            Ok(mut pmtud) => match pmtud.discover() {
-                Ok(mtu) => (
-                    Some(mtu),
-                    // 7.2.1: cwnd should be min(4*MTU, max (2*MTU, 4380 bytes))
-                    Some(cmp::min(4 * mtu, cmp::max(2 * mtu, 4380))),
-                    // 7.2.1; initial value of ssthreshold can be anything
-                    Some(10000),
-                ),
-                _ => (Some(1500), Some(1500), Some(10000)),
-            },
-            _ => (None, None, None),
-        };
+        if !same_port(&local_addrs) {
+            return Err(SCTPError::InvalidLocalAddress);
+        }
+        if !same_port(&remote_addrs) {
+            return Err(SCTPError::InvalidRemoteAddress);
+        }
 
-        let stream = Stream::new(local_addr).await?.connect(remote_addr).await?;
+        let recv_buffer = RecvBuffer::new(config.recv_buffer_size, config.max_plpmtu);
+        let paths = remote_addrs
+            .iter()
+            .map(|addr| Path::new(*addr, &config))
+            .collect();
+
+        let stream = Stream::bind(&local_addrs).await?;
 
         let mut association = Self {
             id: "nra".to_owned(),
             stream,
             rng: thread_rng(),
             msg_queue: VecDeque::new(),
-            local_addr: local_sockaddr,
-            remote_addr: Some(remote_sockaddr),
+            local_addrs,
             init_tag: 0,
-            // section 5.1.1: stream id can be between 0 to min(local OS, remote MIS)-1
-            stream_id: 0,
-            stream_seq_no: 0,
+            peer_ver_tag: 0,
+            // the number of streams is known once the handshake is done
+            outbound_ssns: vec![],
+            unordered_mids: vec![],
             max_retries: ASSOCIATION_MAX_RETRANS,
             max_init_retries: MAX_INIT_RETRANSMITS,
-            rto: RTO_INITIAL * 1000,
             tsn: 0,
-            largest_tsn: 0,
+            recv_buffer,
+            reassembly: ReassemblyQueue::new(config.max_message_size),
+            interleaved_reassembly: InterleavedReassemblyQueue::new(config.max_message_size),
+            inbound_streams: ReorderQueue::new(0),
             remote_rwnd: 0,
-            mtu,
-            cwnd,
-            ssthresh,
+            send_queue: VecDeque::new(),
+            scheduler: Scheduler::new(config.stream_scheduler, 0),
+            outstanding: VecDeque::new(),
+            paths,
+            primary: 0,
+            preferred_primary: 0,
+            error_count: 0,
+            failed: false,
+            notifications: VecDeque::new(),
+            peer_extensions: vec![],
+            peer_forward_tsn: false,
+            peer_adaptation_code_point: None,
+            interleaving: false,
+            nr_sack: false,
+            ecn: false,
+            accept_zero_checksum: config.zero_checksum_edmid.is_some(),
+            send_zero_checksum: false,
+            ecn_sender: EcnSender::new(),
+            ecn_receiver: EcnReceiver::new(),
+            abandoned: AbandonedStats::default(),
+            // the serial nos are known once the handshake is done
+            asconf: AsconfSender::new(0),
+            peer_asconf: AsconfReceiver::new(0),
+            reconfig: ReconfigSender::new(0),
+            peer_reconfig: ReconfigReceiver::new(0),
+            auth: new_authenticator(&config),
+            config,
         };
 
         association.start_sender_4_way_handshake().await?;
+        association.notify_adaptation();
         Ok(association)
     }
 
     /// Creates a new recvr endpoint
-    pub async fn new_recvr(local_addr: impl AsRef<str>) -> Result<Self, SCTPError> {
-        let local_sockaddr: SocketAddr = local_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidLocalAddress)?;
+    pub async fn new_recvr(
+        local_addrs: Vec<SocketAddr>,
+        config: AssociationConfig,
+    ) -> Result<Self, SCTPError> {
+        if !same_port(&local_addrs) {
+            return Err(SCTPError::InvalidLocalAddress);
+        }
 
-        let stream = Stream::new(local_addr).await?;
+        let stream = Stream::bind(&local_addrs).await?;
+        let recv_buffer = RecvBuffer::new(config.recv_buffer_size, config.max_plpmtu);
 
         let mut association = Self {
             id: "nra".to_owned(),
             stream,
             rng: thread_rng(),
             msg_queue: VecDeque::new(),
-            local_addr: local_sockaddr,
-            remote_addr: None,
+            local_addrs,
             init_tag: 0,
-            // section 5.1.1: stream id can be between 0 to min(local OS, remote MIS)-1
-            stream_id: 0,
-            stream_seq_no: 0,
+            peer_ver_tag: 0,
+            // the number of streams is known once the handshake is done
+            outbound_ssns: vec![],
+            unordered_mids: vec![],
             max_retries: ASSOCIATION_MAX_RETRANS,
             max_init_retries: MAX_INIT_RETRANSMITS,
-            rto: RTO_INITIAL * 1000,
             tsn: 0,
-            largest_tsn: 0,
+            recv_buffer,
+            reassembly: ReassemblyQueue::new(config.max_message_size),
+            interleaved_reassembly: InterleavedReassemblyQueue::new(config.max_message_size),
+            inbound_streams: ReorderQueue::new(0),
             remote_rwnd: 0,
-            mtu: None, // we dont know what this is yet!
-            // 7.2.1: cwnd should be min(4*MTU, max (2*MTU, 4380 bytes))
-            // but we dont know what the mtu is yet!
-            cwnd: None,
-            // 7.2.1; initial value of ssthreshold can be anything
-            ssthresh: Some(10000),
+            send_queue: VecDeque::new(),
+            scheduler: Scheduler::new(config.stream_scheduler, 0),
+            outstanding: VecDeque::new(),
+            // the addrs of the peer are known once its INIT arrives
+            paths: vec![],
+            primary: 0,
+            preferred_primary: 0,
+            error_count: 0,
+            failed: false,
+            notifications: VecDeque::new(),
+            peer_extensions: vec![],
+            peer_forward_tsn: false,
+            peer_adaptation_code_point: None,
+            interleaving: false,
+            nr_sack: false,
+            ecn: false,
+            accept_zero_checksum: config.zero_checksum_edmid.is_some(),
+            send_zero_checksum: false,
+            ecn_sender: EcnSender::new(),
+            ecn_receiver: EcnReceiver::new(),
+            abandoned: AbandonedStats::default(),
+            // the serial nos are known once the handshake is done
+            asconf: AsconfSender::new(0),
+            peer_asconf: AsconfReceiver::new(0),
+            reconfig: ReconfigSender::new(0),
+            peer_reconfig: ReconfigReceiver::new(0),
+            auth: new_authenticator(&config),
+            config,
         };
 
         association.start_recvr_4_way_handshake().await?;
+        association.notify_adaptation();
 
         Ok(association)
     }
@@ -152,13 +309,25 @@ impl Association {
             debug!("sending init ...");
             self.send_init().await?;
 
-            match timeout(Duration::from_millis(self.rto), self.stream.recv()).await {
+            match timeout(self.rto(), self.stream.recv_from()).await {
                 Ok(bytes) => {
-                    let init_ack = InitAck::from(bytes?);
+                    let packet = self.parse_packet(bytes?.0)?;
+                    let init_ack = packet
+                        .find_chunk(ChunkType::InitAck)
+                        .map(|chunk| InitAck::from(chunk.get_bytes()))
+                        .ok_or(SCTPError::AssociationSetupError)?;
                     debug!("recvd: {:?}", init_ack);
+                    self.peer_ver_tag = init_ack.init_tag;
+                    // section 6.2.1 A)
+                    self.remote_rwnd = init_ack.a_rwnd;
+                    self.recv_buffer.set_initial_tsn(init_ack.init_tsn);
+                    self.peer_asconf = AsconfReceiver::new(init_ack.init_tsn);
+                    self.peer_reconfig = ReconfigReceiver::new(init_ack.init_tsn);
+                    self.negotiate_streams(init_ack.num_ob_streams, init_ack.num_ib_streams)?;
 
                     match init_ack.optional_params {
                         Some(params) => {
+                            self.on_peer_params(&params);
                             let param = params
                                 .iter()
                                 .find(|param| param.param_type == ParamType::StateCookie)
@@ -185,10 +354,7 @@ impl Association {
     async fn attempt_cookie_echo_and_ack(&self, cookie: Cookie) -> Result<(), SCTPError> {
         let mut num_retries = 0;
 
-        let mut packet = Packet::new(
-            self.local_addr.port(),
-            self.remote_addr.as_ref().unwrap().port(),
-        );
+        let mut packet = self.new_packet();
         packet.add_chunk(Box::new(CookieEcho::new(cookie)));
 
         while num_retries < self.max_retries {
@@ -197,10 +363,12 @@ impl Association {
 
             // TODO abhi - timeout duration of cookie timer isn't hardcoded;
             // it is calculated from RTO (which is 3 secs to begin with according to the RFC)
-            match timeout(Duration::from_millis(self.rto), self.stream.recv()).await {
+            match timeout(self.rto(), self.stream.recv_from()).await {
                 Ok(bytes) => {
-                    let _ = CookieAck::from(bytes?);
-                    return Ok(());
+                    let packet = self.parse_packet(bytes?.0)?;
+                    if packet.find_chunk(ChunkType::CookieAck).is_some() {
+                        return Ok(());
+                    }
                 }
                 _ => {
                     num_retries += 1;
@@ -217,11 +385,31 @@ impl Association {
     async fn start_recvr_4_way_handshake(&mut self) -> Result<(), SCTPError> {
         debug!("waiting ...");
         let (bytes, remote_addr) = self.stream.recv_from().await?;
-        let init = Init::from(bytes);
-        self.remote_addr = Some(remote_addr);
+        let init = self
+            .parse_packet(bytes)?
+            .find_chunk(ChunkType::Init)
+            .map(|chunk| Init::from(chunk.get_bytes()))
+            .ok_or(SCTPError::AssociationSetupError)?;
         debug!("recvd: {:?}", init);
-        let cookie = self.send_init_ack(init).await?;
-        let cookie_echo = CookieEcho::from(self.stream.recv().await?);
+        // section 5.1.2: the source addr of the INIT along with the addrs
+        // listed in it are the transport addrs of the peer
+        self.paths.push(Path::new(remote_addr, &self.config));
+        if let Some(params) = init.optional_params.as_ref() {
+            self.on_peer_params(params);
+        }
+        self.peer_ver_tag = init.init_tag;
+        // section 6.2.1 A)
+        self.remote_rwnd = init.a_rwnd;
+        self.recv_buffer.set_initial_tsn(init.init_tsn);
+        self.peer_asconf = AsconfReceiver::new(init.init_tsn);
+        self.peer_reconfig = ReconfigReceiver::new(init.init_tsn);
+        self.negotiate_streams(init.num_ob_streams, init.num_ib_streams)?;
+        let cookie = self.send_init_ack().await?;
+        let cookie_echo = self
+            .parse_packet(self.stream.recv_from().await?.0)?
+            .find_chunk(ChunkType::CookieEcho)
+            .map(|chunk| CookieEcho::from(chunk.get_bytes()))
+            .ok_or(SCTPError::AssociationSetupError)?;
 
         if cookie == cookie_echo.cookie {
             self.send_cookie_ack().await?;
@@ -237,38 +425,171 @@ impl Association {
     /// Sends a packet with init chunk
     async fn send_init(&mut self) -> Result<(), SCTPError> {
         let ver_tag: u32 = self.rng.gen_range(1..=4294967295);
-        let mut packet = Packet::new(
-            self.local_addr.port(),
-            self.remote_addr.as_ref().unwrap().port(),
-        );
+        self.init_tag = ver_tag;
+        // the initial TSN is the same as the init tag
+        self.tsn = ver_tag.wrapping_sub(1);
+        self.asconf = AsconfSender::new(ver_tag);
+        self.reconfig = ReconfigSender::new(ver_tag);
+        // section 8.5.1: the verification tag of a packet containing INIT must be 0
+        let mut packet = Packet::new(self.local_addrs[0].port(), self.primary_addr().port(), 0);
 
-        // TODO abhi - figure what the buffer size should be
-        let a_rwnd = 10000;
-        packet.add_chunk(Box::new(Init::new(ver_tag, a_rwnd, 1, 1, None)));
+        let a_rwnd = self.recv_buffer.a_rwnd();
+        packet.add_chunk(Box::new(Init::new(
+            ver_tag,
+            a_rwnd,
+            self.config.num_outbound_streams,
+            self.config.max_inbound_streams,
+            Some(self.init_params()),
+        )));
 
-        self.stream.send(&Vec::<u8>::from(&packet)).await?;
-        Ok(())
+        self.send_packet(&packet, self.primary_addr()).await
     }
 
     /// Sends a packet with init ack chunk
-    async fn send_init_ack(&mut self, init: Init) -> Result<Cookie, SCTPError> {
+    async fn send_init_ack(&mut self) -> Result<Cookie, SCTPError> {
         debug!("sending init ack");
-        let mut packet = Packet::new(
-            self.local_addr.port(),
-            self.remote_addr.as_ref().unwrap().port(),
+        let mut packet = self.new_packet();
+        self.init_tag = self.rng.gen_range(1..=4294967295);
+        let a_rwnd = self.recv_buffer.a_rwnd();
+        let mut init_ack = InitAck::new(
+            self.init_tag,
+            a_rwnd,
+            self.config.num_outbound_streams,
+            self.config.max_inbound_streams,
+            Some(self.init_params()),
         );
-        let mut init_ack = InitAck::new(init);
+        self.tsn = init_ack.init_tsn.wrapping_sub(1);
+        self.asconf = AsconfSender::new(init_ack.init_tsn);
+        self.reconfig = ReconfigSender::new(init_ack.init_tsn);
         let cookie = Cookie::new();
         init_ack.add_param(Parameter::new(ParamType::StateCookie, (&cookie).into()));
         packet.add_chunk(Box::new(init_ack));
 
-        self.stream.send(&Vec::<u8>::from(&packet)).await?;
+        self.send_packet(&packet, self.primary_addr()).await?;
         Ok(cookie)
     }
 
+    /// Returns the optional params of our INIT/INIT ACK
+    fn init_params(&self) -> Vec<Parameter> {
+        let mut params = vec![];
+        // section 5.1.2: a single homed endpoint doesn't list its addr
+        // as the peer learns it from the source addr of our packets
+        if self.local_addrs.len() > 1 {
+            params.extend(
+                self.local_addrs
+                    .iter()
+                    .map(|addr| Parameter::from_ip(addr.ip())),
+            );
+        }
+        let mut extensions = vec![RECONFIG_CHUNK_TYPE];
+        // RFC 5061 section 4.1.1: ASCONFs must be authenticated so they are only
+        // supported along with AUTH
+        if self.config.auth_enabled {
+            params.extend(self.auth.params());
+            extensions.push(AUTH_CHUNK_TYPE);
+            extensions.extend(ASCONF_CHUNK_TYPES);
+        }
+        if self.config.interleaving_enabled {
+            extensions.extend([I_DATA_CHUNK_TYPE, I_FORWARD_TSN_CHUNK_TYPE]);
+        }
+        if self.config.nr_sack_enabled {
+            extensions.push(NR_SACK_CHUNK_TYPE);
+        }
+        params.push(Parameter::supported_extensions(&extensions));
+        params.push(Parameter::new(ParamType::ForwardTsnSupported, vec![]));
+        if self.config.ecn_enabled {
+            params.push(Parameter::new(ParamType::EcnCapable, vec![]));
+        }
+        if let Some(edmid) = self.config.zero_checksum_edmid {
+            params.push(Parameter::zero_checksum_acceptable(edmid));
+        }
+        if let Some(code_point) = self.config.adaptation_code_point {
+            params.push(Parameter::adaptation_layer_indication(code_point));
+        }
+        params
+    }
+
+    /// Learns the addrs and the extensions of the peer from its INIT/INIT ACK
+    fn on_peer_params(&mut self, params: &[Parameter]) {
+        self.add_peer_addrs(params);
+        if let Some(param) = params
+            .iter()
+            .find(|param| param.param_type == ParamType::SupportedExtensions)
+        {
+            self.peer_extensions = param.value.clone();
+        }
+        self.peer_forward_tsn = params
+            .iter()
+            .any(|param| param.param_type == ParamType::ForwardTsnSupported);
+        self.peer_adaptation_code_point = params
+            .iter()
+            .find_map(|param| param.adaptation_code_point());
+        self.interleaving =
+            self.config.interleaving_enabled && self.peer_extensions.contains(&I_DATA_CHUNK_TYPE);
+        self.scheduler.set_interleaving(self.interleaving);
+        self.inbound_streams.set_interleaving(self.interleaving);
+        self.nr_sack =
+            self.config.nr_sack_enabled && self.peer_extensions.contains(&NR_SACK_CHUNK_TYPE);
+        // RFC 4960 appendix A: ECN is only used if both endpoints are ECN capable
+        self.ecn = self.config.ecn_enabled
+            && params
+                .iter()
+                .any(|param| param.param_type == ParamType::EcnCapable);
+        if self.ecn {
+            if let Err(e) = self.stream.set_ecn(true) {
+                debug!("not using ecn: {}", e);
+                self.ecn = false;
+            }
+        }
+        // RFC 9653 section 5.2: zero checksums are only sent if the peer accepts
+        // them with the method we use
+        self.send_zero_checksum = self
+            .config
+            .zero_checksum_edmid
+            .is_some_and(|edmid| params.iter().any(|param| param.edmid() == Some(edmid)));
+        if self.config.auth_enabled {
+            self.auth.on_peer_params(params);
+        }
+    }
+
+    /// Adds a path for every addr the peer listed in its INIT/INIT ACK
+    fn add_peer_addrs(&mut self, params: &[Parameter]) {
+        // all the addrs of the peer share the port of its first one
+        let port = self.primary_addr().port();
+        for ip in params.iter().filter_map(|param| param.ip()) {
+            let addr = SocketAddr::new(ip, port);
+            if self.paths.iter().all(|path| path.addr() != addr) {
+                debug!("peer addr {}", addr);
+                self.paths.push(Path::new(addr, &self.config));
+            }
+        }
+    }
+
+    /// Sets up the streams from the counts announced by the peer in its INIT/INIT ACK.
+    /// section 5.1.1: we can only send on min(local OS, peer MIS) streams
+    /// and the peer can only send on min(peer OS, local MIS) streams
+    fn negotiate_streams(&mut self, peer_os: u16, peer_mis: u16) -> Result<(), SCTPError> {
+        // section 3.3.2: OS and MIS must not be 0
+        if peer_os == 0 || peer_mis == 0 {
+            return Err(SCTPError::AssociationSetupError);
+        }
+
+        let num_outbound = peer_mis.min(self.config.num_outbound_streams);
+        let num_inbound = peer_os.min(self.config.max_inbound_streams);
+        debug!("{} outbound, {} inbound streams", num_outbound, num_inbound);
+        self.outbound_ssns = vec![0; num_outbound as usize];
+        self.unordered_mids = vec![0; num_outbound as usize];
+        self.inbound_streams = ReorderQueue::new(num_inbound);
+        self.scheduler = Scheduler::new(self.config.stream_scheduler, num_outbound);
+        // the recvr learns whether I-DATA is used before it sets up the streams
+        self.scheduler.set_interleaving(self.interleaving);
+        self.inbound_streams.set_interleaving(self.interleaving);
+        Ok(())
+    }
+
     /// Sends a packet with cookie echo chunk
     async fn send_cookie_echo(&self, packet: &Packet) -> Result<(), SCTPError> {
-        self.stream.send(&Vec::<u8>::from(packet)).await?;
+        self.send_packet(packet, self.primary_addr()).await?;
 
         Ok(())
     }
@@ -276,156 +597,1916 @@ impl Association {
     /// Sends a packet with cookie ack chunk
     async fn send_cookie_ack(&self) -> Result<(), SCTPError> {
         debug!("sending cookie ack");
-        let mut packet = Packet::new(
-            self.local_addr.port(),
-            self.remote_addr.as_ref().unwrap().port(),
-        );
+        let mut packet = self.new_packet();
 
         packet.add_chunk(Box::new(CookieAck::new()));
 
-        self.stream.send(&Vec::<u8>::from(&packet)).await?;
+        self.send_packet(&packet, self.primary_addr()).await?;
         Ok(())
     }
 
-    /// Sends user data
-    pub async fn send(&mut self, user_data: &[u8]) -> Result<(), SCTPError> {
-        // section 6.1 rule A)
-        if self.remote_rwnd == 0 {
-            // TODO abhi: send a zero probe
-            return Err(SCTPError::RemoteBufferFull);
+    /// Creates a packet addressed to the peer
+    fn new_packet(&self) -> Packet {
+        let addr = self.primary_addr();
+        let mut packet = Packet::new(self.local_addrs[0].port(), addr.port(), self.peer_ver_tag);
+        let min_len = (self.config.min_packet_size as usize)
+            .saturating_sub(ip_header_size(&addr) + UDP_HEADER_SIZE)
+            .min(self.max_packet_size());
+        packet.pad_to(min_len);
+        packet.set_zero_checksum(self.send_zero_checksum);
+        packet
+    }
+
+    /// Parses a packet recvd from the peer, accepting a zero checksum once we announced
+    /// an alternate error detection method
+    fn parse_packet(&self, bytes: Vec<u8>) -> Result<Packet, SCTPError> {
+        Packet::parse(bytes, self.accept_zero_checksum)
+    }
+
+    /// Sends a packet to the given transport addr of the peer
+    /// The chunks the peer wants authenticated are sent behind an AUTH chunk.
+    /// A packet larger than the PMTU of the path, such as a chunk fragmented before
+    /// the PMTU dropped, is sent without the DF bit so routers can fragment it.
+    async fn send_packet(&self, packet: &Packet, addr: SocketAddr) -> Result<(), SCTPError> {
+        let bytes = self.encode_packet(packet);
+        let pmtu = self
+            .paths
+            .iter()
+            .find(|path| path.addr() == addr)
+            .map(|path| path.pmtu() as usize);
+        if pmtu.is_some_and(|pmtu| ip_header_size(&addr) + UDP_HEADER_SIZE + bytes.len() > pmtu) {
+            return self.stream.send_fragmentable_to(&bytes, addr).await;
         }
+        self.stream.send_to(&bytes, addr).await
+    }
+
+    /// Returns the wire format of a packet.
+    /// RFC 4895 section 6.2: the AUTH chunk covers every chunk after it
+    /// so it goes in front of the first chunk that needs it
+    fn encode_packet(&self, packet: &Packet) -> Vec<u8> {
+        match packet
+            .chunks
+            .iter()
+            .position(|chunk| self.auth.peer_requires_auth(u8::from(&chunk.chunk_type())))
+        {
+            Some(idx) => packet.to_authenticated_bytes(idx, |covered| self.auth.sign(covered)),
+            None => Vec::<u8>::from(packet),
+        }
+    }
+
+    /// Returns the transport addr new DATA is sent to
+    fn primary_addr(&self) -> SocketAddr {
+        self.paths[self.primary].addr()
+    }
+
+    /// Returns the current RTO of the primary path
+    fn rto(&self) -> Duration {
+        self.paths[self.primary].rto.rto()
+    }
 
-        // if self.cwnd.as_ref().unwrap() > 0
+    /// Sends user data on the stream given in the options
+    pub async fn send(&mut self, user_data: &[u8], options: SendOptions) -> Result<(), SCTPError> {
+        if self.failed {
+            return Err(SCTPError::AssociationFailed);
+        }
+        if user_data.len() > self.config.max_message_size {
+            return Err(SCTPError::MessageTooLarge);
+        }
+        // section 5.1.1: stream id can be between 0 to min(local OS, remote MIS)-1
+        let next_ssn = *self
+            .outbound_ssns
+            .get(options.stream_id as usize)
+            .ok_or(SCTPError::InvalidStreamId)?;
+        // section 6.6: the stream seq no of an unordered msg is ignored by the peer
+        // so it doesn't use up one of the stream's seq nos
+        let stream_seq_no = if options.unordered {
+            0
+        } else {
+            next_ssn as u16
+        };
+        // RFC 8260 section 2.1: ordered and unordered msgs have their own message ids
+        let message_id = if options.unordered {
+            self.unordered_mids[options.stream_id as usize]
+        } else {
+            next_ssn
+        };
 
         // section 6 note 1)
-        let mtu = *self.mtu.as_ref().unwrap() as usize;
-        let payload_size = mtu - 20 - 12 - 16; // 20 bytes ip header +  12 bytes sctp packet header + 16 bytes Data chunk header
+        let payload_size = self.max_payload_size();
+        let abandonment = Abandonment::new(options.pr_policy, Instant::now());
+        if let Some(priority) = abandonment.priority() {
+            self.make_room(user_data.len(), priority);
+        }
 
-        if user_data.len() > mtu {
-            // TODO abhi - fragment data into multiple chunks
+        if user_data.len() > payload_size {
+            // section 6.9: fragment data into multiple chunks.
+            // all the fragments carry the same stream seq no
             let mtu_sized_chunks = user_data.chunks(payload_size);
             let len = mtu_sized_chunks.len();
 
             for (i, mtu_sized_chunk) in mtu_sized_chunks.enumerate() {
-                self.send_and_wait(i == 0, i == len - 1, mtu_sized_chunk);
+                self.queue_data(
+                    &options,
+                    stream_seq_no,
+                    Fragment {
+                        message_id,
+                        fsn: i as u32,
+                    },
+                    i == len - 1,
+                    mtu_sized_chunk,
+                    abandonment,
+                );
             }
         } else {
             // we can send the entire user data in a single data chunk
-            self.send_and_wait(true, true, user_data);
+            let fragment = Fragment { message_id, fsn: 0 };
+            self.queue_data(
+                &options,
+                stream_seq_no,
+                fragment,
+                true,
+                user_data,
+                abandonment,
+            );
+        }
+
+        if options.unordered {
+            self.unordered_mids[options.stream_id as usize] = message_id.wrapping_add(1);
+        } else {
+            self.outbound_ssns[options.stream_id as usize] = next_ssn.wrapping_add(1);
         }
 
-        self.stream_seq_no += 1 % 65535;
+        self.flush_to(self.config.send_buffer_size).await
+    }
 
-        Ok(())
+    /// Waits until all the data sent so far is acked or abandoned
+    pub async fn flush(&mut self) -> Result<(), SCTPError> {
+        if self.failed {
+            return Err(SCTPError::AssociationFailed);
+        }
+        self.flush_to(0).await
+    }
+
+    /// Returns the largest payload a single DATA chunk can carry without
+    /// the packet exceeding the PMTU of any path.
+    /// It follows the PMTUs found by PLPMTUD for the msgs sent from then on.
+    fn max_payload_size(&self) -> usize {
+        let chunk_header_size = if self.interleaving {
+            I_DATA_CHUNK_HEADER_SIZE
+        } else {
+            DATA_CHUNK_HEADER_SIZE
+        };
+        let payload_size = self.max_packet_size() - COMMON_HEADER_SIZE - chunk_header_size;
+        // keep fragments 4 byte aligned so no padding is needed
+        payload_size - payload_size % 4
+    }
+
+    /// Returns the largest SCTP packet that fits in the PMTU of every path.
+    /// A chunk can be retransmitted to any of the addrs of the peer.
+    fn max_packet_size(&self) -> usize {
+        self.paths
+            .iter()
+            .map(|path| path.pmtu() as usize - ip_header_size(&path.addr()) - UDP_HEADER_SIZE)
+            .min()
+            .unwrap_or(self.config.max_plpmtu as usize - IPV4_HEADER_SIZE - UDP_HEADER_SIZE)
+    }
+
+    /// Returns the size of the IP packet a single DATA chunk is sent in to the given addr
+    fn data_packet_size(&self, chunk: &Data, addr: &SocketAddr) -> u32 {
+        let chunk_header_size = if self.interleaving {
+            I_DATA_CHUNK_HEADER_SIZE
+        } else {
+            DATA_CHUNK_HEADER_SIZE
+        };
+        let payload_len = chunk.payload_len() + padding(chunk.payload_len());
+        (ip_header_size(addr)
+            + UDP_HEADER_SIZE
+            + COMMON_HEADER_SIZE
+            + chunk_header_size
+            + payload_len) as u32
     }
 
-    async fn send_and_wait(
+    /// Queues up the payload for transmission.
+    /// The first fragment of a msg has fragment seq no 0.
+    fn queue_data(
         &mut self,
-        start: bool,
+        options: &SendOptions,
+        stream_seq_no: u16,
+        fragment: Fragment,
         end: bool,
         payload: &[u8],
-    ) -> Result<(), SCTPError> {
-        let mut packet = Packet::new(
-            self.local_addr.port(),
-            self.remote_addr.as_ref().unwrap().port(),
-        );
+        abandonment: Abandonment,
+    ) {
+        self.send_queue.push_back(QueuedChunk {
+            chunk: Data::new(
+                0,
+                options.stream_id,
+                stream_seq_no,
+                options.ppid,
+                fragment.fsn == 0,
+                end,
+                payload.to_vec(),
+            )
+            .unordered(options.unordered),
+            fragment,
+            abandonment,
+        });
+    }
 
-        self.tsn += 1;
+    /// Returns the number of bytes sent but not acked yet.
+    /// RFC 3758 section 3.5 A3) abandoned chunks aren't in flight anymore
+    fn flight_size(&self) -> u32 {
+        self.outstanding
+            .iter()
+            .filter(|outstanding| !outstanding.abandoned)
+            .map(|outstanding| outstanding.chunk.payload_len() as u32)
+            .sum()
+    }
 
-        packet.add_chunk(Box::new(Data::new(
-            self.tsn,
-            self.stream_id,
-            self.stream_seq_no,
-            0,
-            start,
-            end,
-            payload.to_vec(),
-        )));
+    /// Returns the number of bytes sent on the given path but not acked yet
+    fn path_flight_size(&self, path: usize) -> u32 {
+        self.outstanding
+            .iter()
+            .filter(|outstanding| outstanding.path == path && !outstanding.abandoned)
+            .map(|outstanding| outstanding.chunk.payload_len() as u32)
+            .sum()
+    }
+
+    /// Returns the number of bytes of user data queued or in flight
+    fn buffered_size(&self) -> usize {
+        let queued: usize = self
+            .send_queue
+            .iter()
+            .map(|queued| queued.chunk.payload_len())
+            .sum();
+        queued + self.flight_size() as usize
+    }
+
+    /// Transmits queued DATA chunks and processes SACKs until no more than `limit` bytes
+    /// are left unacked, or until all of them are acked if `limit` is 0
+    async fn flush_to(&mut self, limit: usize) -> Result<(), SCTPError> {
+        loop {
+            self.handle_plpmtud_timers().await?;
+            self.abandon_expired();
+            self.transmit().await?;
+            self.send_forward_tsn().await?;
+            if self.send_queue.is_empty() && self.outstanding.is_empty()
+                || limit > 0 && self.buffered_size() <= limit
+            {
+                return Ok(());
+            }
+            self.wait_for_sack().await?;
+        }
+    }
+
+    /// Transmits as many queued DATA chunks to the primary path as the peer's rwnd
+    /// and the cwnd of the path allow
+    async fn transmit(&mut self) -> Result<(), SCTPError> {
+        let path = self.primary;
+
+        while let Some(idx) = self.next_queued() {
+            let queued = &self.send_queue[idx];
+            let len = queued.chunk.payload_len() as u32;
+            let flight_size = self.flight_size();
+
+            // section 6.1 rule A)
+            if self.remote_rwnd < len {
+                // the peer has no room for this chunk but we can always
+                // have one chunk in flight to probe its window
+                if flight_size > 0 {
+                    break;
+                }
+                debug!("sending zero window probe");
+            }
+
+            // section 6.1 rule B)
+            if self.path_flight_size(path) >= self.paths[path].cc.cwnd() {
+                break;
+            }
+
+            let queued = self.send_queue.remove(idx).unwrap();
+            self.tsn = self.tsn.wrapping_add(1);
+            let chunk = queued.chunk.with_tsn(self.tsn);
+            self.scheduler
+                .on_sent(chunk.stream_id(), chunk.payload_len(), chunk.is_end());
+            self.send_data(&chunk, queued.fragment, path).await?;
+            // the path isn't idle anymore
+            self.restart_heartbeat_timer(path);
+            // section 6.2.1 B)
+            self.remote_rwnd = self.remote_rwnd.saturating_sub(len);
+            self.outstanding.push_back(OutstandingChunk {
+                chunk,
+                fragment: queued.fragment,
+                sent_at: Instant::now(),
+                retransmissions: 0,
+                path,
+                abandonment: queued.abandonment,
+                abandoned: false,
+            });
+        }
 
-        self.stream.send(&Vec::<u8>::from(&packet)).await?;
-        self.wait_for_sack().await;
         Ok(())
     }
 
+    /// Returns the index of the queued chunk the scheduler picks to be sent next.
+    /// Only the oldest queued chunk of every stream can be picked.
+    fn next_queued(&self) -> Option<usize> {
+        let mut heads: Vec<(u16, usize)> = vec![];
+        for (idx, queued) in self.send_queue.iter().enumerate() {
+            let stream_id = queued.chunk.stream_id();
+            if !heads.iter().any(|&(other, _)| other == stream_id) {
+                heads.push((stream_id, idx));
+            }
+        }
+        let streams: Vec<u16> = heads.iter().map(|&(stream_id, _)| stream_id).collect();
+        let stream_id = self.scheduler.pick(&streams)?;
+        heads
+            .into_iter()
+            .find(|&(other, _)| other == stream_id)
+            .map(|(_, idx)| idx)
+    }
+
+    /// Sends a packet with a single DATA chunk on the given path,
+    /// as an I-DATA chunk if interleaving has been negotiated
+    async fn send_data(
+        &self,
+        chunk: &Data,
+        fragment: Fragment,
+        path: usize,
+    ) -> Result<(), SCTPError> {
+        let mut packet = self.new_packet();
+        if self.interleaving {
+            packet.add_chunk(Box::new(
+                IData::new(
+                    chunk.tsn(),
+                    chunk.stream_id(),
+                    fragment.message_id,
+                    fragment.fsn,
+                    chunk.payload_proto_id(),
+                    chunk.is_beginning(),
+                    chunk.is_end(),
+                    chunk.payload().to_vec(),
+                )
+                .unordered(chunk.is_unordered()),
+            ));
+        } else {
+            packet.add_chunk(Box::new(chunk.clone()));
+        }
+        self.send_packet(&packet, self.paths[path].addr()).await
+    }
+
+    /// Waits for a SACK and processes it.
+    /// Handles the expiration of the T3-rtx timer if none arrives in time.
     async fn wait_for_sack(&mut self) -> Result<(), SCTPError> {
-        // section 6.3.2: start retransmission timer
+        // section 6.3.2: the retransmission timer runs on the path
+        // the earliest outstanding chunk was sent on
+        let path = self
+            .outstanding
+            .front()
+            .map_or(self.primary, |outstanding| outstanding.path);
         loop {
-            match timeout(Duration::from_millis(self.rto), self.stream.recv()).await {
-                Ok(bytes) => {
-                    if let Ok(packet) = Packet::try_from(bytes.unwrap()) {
-                        for chunk in packet.chunks {
-                            match chunk.chunk_type() {
-                                Data => {}
-                                Init => {}
-                                InitAck => {}
-                                Sack => {
-                                    let sack = Sack::from(chunk.get_bytes());
-                                    if sack.cumulative_tsn_ack == self.tsn {
-                                        return Ok(());
-                                    }
-                                }
-                                Abort => {}
-                                Shutdown => {}
-                                CookieAck => {}
-                                CookieEcho => {}
-                                ShutdownComplete => {}
-                                ShutdownAck => {}
-                                Invalid => {}
-                            }
+            match timeout(self.paths[path].rto.rto(), self.stream.recv_from()).await {
+                Ok(result) => {
+                    let (bytes, from) = result?;
+                    if let Ok(packet) = self.parse_packet(bytes) {
+                        if self.handle_packet(packet, from).await? {
+                            return Ok(());
                         }
                     }
                 }
-                _ => {
-                    // 6.3.3.  Handle T3-rtx Expiration E1)
-                    self.ssthresh = Some(cmp::max(self.cwnd.unwrap() / 2, 4 * self.mtu.unwrap()));
-                    self.cwnd = self.mtu;
-
-                    // 6.3.3.  Handle T3-rtx Expiration E2)
-                    self.rto = self.rto * 2;
-
-                    // this is not mentioned in the rfc in the form of a clause
-                    // but in my opinion, it only makes sense to quit waiting
-                    // if the rto has reached/crossed its max value
-                    if (self.rto / 1000) > RTO_MAX.into() {
-                        return Err(SCTPError::RetransmissionTimeout);
-                    }
+                _ => return self.handle_t3_expiration(path).await,
+            }
+        }
+    }
+
+    /// Processes a SACK from the peer
+    /// The chunks in the NR gap ack blocks of an NR-SACK are freed like the cumulatively
+    /// acked ones since the peer will never renege on them
+    fn handle_sack(
+        &mut self,
+        cumulative_tsn_ack: u32,
+        a_rwnd: u32,
+        nr_gap_ack_blocks: &[(u16, u16)],
+    ) {
+        let flight_sizes: Vec<u32> = (0..self.paths.len())
+            .map(|path| self.path_flight_size(path))
+            .collect();
+        let mut bytes_acked = vec![0; self.paths.len()];
+        let mut rtts = vec![None; self.paths.len()];
 
-                    // 6.3.3.  Handle T3-rtx Expiration E3)
-                    // TODO abhi: handle this case
+        let is_acked = |tsn: u32| {
+            tsn_lte(tsn, cumulative_tsn_ack)
+                || nr_gap_ack_blocks.iter().any(|&(start, end)| {
+                    let offset = tsn.wrapping_sub(cumulative_tsn_ack);
+                    offset >= start as u32 && offset <= end as u32
+                })
+        };
+        let mut idx = 0;
+        while let Some(outstanding) = self.outstanding.get(idx) {
+            let tsn = outstanding.chunk.tsn();
+            if !is_acked(tsn) {
+                // without NR gap ack blocks nothing past the cumulative TSN ack is freed
+                if nr_gap_ack_blocks.is_empty() {
+                    break;
                 }
+                idx += 1;
+                continue;
             }
+            let outstanding = self.outstanding.remove(idx).unwrap();
+            if outstanding.abandoned {
+                continue;
+            }
+            bytes_acked[outstanding.path] += outstanding.chunk.payload_len() as u32;
+            if outstanding.retransmissions == 0 {
+                rtts[outstanding.path] = Some(outstanding.sent_at.elapsed());
+            }
+        }
+
+        for path in 0..self.paths.len() {
+            if let Some(rtt) = rtts[path] {
+                self.paths[path].rto.on_measurement(rtt);
+            }
+            if bytes_acked[path] > 0 {
+                self.paths[path]
+                    .cc
+                    .on_ack(bytes_acked[path], flight_sizes[path], rtts[path]);
+                // section 8.3: outstanding data has been acked so the path is reachable
+                self.on_peer_reachable(path);
+                self.paths[path].plpmtud.on_data_acked();
+            }
+        }
+
+        // section 6.2.1 D) iv): rwnd is what the peer advertised minus
+        // whatever is still in flight
+        self.remote_rwnd = a_rwnd.saturating_sub(self.flight_size());
+    }
+
+    /// Handles the expiration of the T3-rtx timer of a path (section 6.3.3)
+    async fn handle_t3_expiration(&mut self, path: usize) -> Result<(), SCTPError> {
+        // 6.3.3.  Handle T3-rtx Expiration E1)
+        self.paths[path].cc.on_timeout();
+        // section 8.1 and 8.2
+        self.on_timeout_error(path)?;
+
+        // 6.3.3.  Handle T3-rtx Expiration E2)
+        self.paths[path].rto.backoff();
+
+        // 6.3.3.  Handle T3-rtx Expiration E3)
+        // the earliest chunk that isn't abandoned is retransmitted. RFC 3758 section 3.5 A2)
+        // a chunk whose lifetime or retransmissions ran out is abandoned instead
+        let idx = loop {
+            let idx = match self
+                .outstanding
+                .iter()
+                .position(|outstanding| !outstanding.abandoned)
+            {
+                Some(idx) => idx,
+                None => return Ok(()),
+            };
+            let outstanding = &self.outstanding[idx];
+            if self.peer_forward_tsn
+                && (outstanding.abandonment.is_expired(Instant::now())
+                    || outstanding
+                        .abandonment
+                        .is_exhausted(outstanding.retransmissions))
+            {
+                self.abandon_msg(idx);
+            } else {
+                break idx;
+            }
+        };
+
+        // RFC 8899 section 4.3: DATA larger than the base PLPMTU that keeps getting lost
+        // may be falling into a black hole
+        let size = self.data_packet_size(&self.outstanding[idx].chunk, &self.paths[path].addr());
+        if self.paths[path].plpmtud.on_data_lost(size, Instant::now()) {
+            self.on_pmtu_change(path);
+        }
+
+        // section 6.4.1: the retransmission goes to an alternate active destination
+        let alternate = self.alternate_path(path);
+        let outstanding = &mut self.outstanding[idx];
+        outstanding.retransmissions += 1;
+        outstanding.path = alternate;
+        let (chunk, fragment) = (outstanding.chunk.clone(), outstanding.fragment);
+        self.send_data(&chunk, fragment, alternate).await?;
+        self.remote_rwnd = self.remote_rwnd.saturating_sub(chunk.payload_len() as u32);
+
+        Ok(())
+    }
+
+    /// Returns the chunk at `idx` of the outstanding chunks followed by the queued ones,
+    /// along with the msg it belongs to, its abandonment rule and whether it has been abandoned.
+    /// Together they hold every chunk that hasn't been acked yet.
+    fn unacked(&self, idx: usize) -> (MessageKey, Abandonment, bool) {
+        match self.outstanding.get(idx) {
+            Some(outstanding) => (
+                MessageKey::new(&outstanding.chunk, outstanding.fragment),
+                outstanding.abandonment,
+                outstanding.abandoned,
+            ),
+            None => {
+                let queued = &self.send_queue[idx - self.outstanding.len()];
+                (
+                    MessageKey::new(&queued.chunk, queued.fragment),
+                    queued.abandonment,
+                    false,
+                )
+            }
+        }
+    }
+
+    /// Abandons the msgs whose lifetime is over, whether they have been sent or not
+    fn abandon_expired(&mut self) {
+        if !self.peer_forward_tsn {
+            return;
+        }
+
+        let now = Instant::now();
+        // abandoning a msg removes its queued chunks so the search starts over every time
+        while let Some(idx) = (0..self.outstanding.len() + self.send_queue.len()).find(|&idx| {
+            let (_, abandonment, abandoned) = self.unacked(idx);
+            !abandoned && abandonment.is_expired(now)
+        }) {
+            self.abandon_msg(idx);
+        }
+    }
+
+    /// Makes room in the send buffer for a msg of `len` bytes with the given priority
+    /// by abandoning msgs of a lower priority, the lowest and most recent ones first
+    /// (RFC 7496 section 3.2)
+    fn make_room(&mut self, len: usize, priority: u16) {
+        let limit = self.config.send_buffer_size;
+        if !self.peer_forward_tsn || limit == 0 {
+            return;
+        }
+
+        while self.buffered_size() + len > limit {
+            let victim = (0..self.outstanding.len() + self.send_queue.len())
+                .filter_map(|idx| match self.unacked(idx) {
+                    (_, abandonment, false) => abandonment
+                        .priority()
+                        .filter(|&other| other > priority)
+                        .map(|other| (other, idx)),
+                    _ => None,
+                })
+                .max();
+            match victim {
+                Some((_, idx)) => self.abandon_msg(idx),
+                None => return,
+            }
+        }
+    }
+
+    /// Abandons the msg the unacked chunk at `idx` belongs to.
+    /// RFC 3758 section 3.5 A1) all the fragments of a msg are abandoned together.
+    /// The ones that haven't been sent yet are dropped before they get a TSN.
+    fn abandon_msg(&mut self, idx: usize) {
+        let (msg, abandonment, _) = self.unacked(idx);
+        let policy = abandonment.policy();
+        let mut bytes = 0;
+        let mut sent = false;
+        for outstanding in self
+            .outstanding
+            .iter_mut()
+            .filter(|outstanding| MessageKey::new(&outstanding.chunk, outstanding.fragment) == msg)
+        {
+            outstanding.abandoned = true;
+            bytes += outstanding.chunk.payload_len();
+            sent = true;
         }
+        self.send_queue.retain(|queued| {
+            let same_msg = MessageKey::new(&queued.chunk, queued.fragment) == msg;
+            if same_msg {
+                bytes += queued.chunk.payload_len();
+            }
+            !same_msg
+        });
+        debug!("abandoning msg of {} bytes, sent: {}", bytes, sent);
+        self.abandoned.record(policy, sent, bytes);
+    }
+
+    /// Sets the priority of an outbound stream for the priority scheduler.
+    /// The lower the value, the sooner its msgs are sent.
+    pub fn set_stream_priority(&mut self, stream_id: u16, priority: u16) -> Result<(), SCTPError> {
+        self.scheduler.set_priority(stream_id, priority)
+    }
+
+    /// Sets the weight of an outbound stream for the weighted fair queueing scheduler
+    pub fn set_stream_weight(&mut self, stream_id: u16, weight: u16) -> Result<(), SCTPError> {
+        self.scheduler.set_weight(stream_id, weight)
+    }
+
+    /// Returns the number of msgs and bytes abandoned under each PR policy
+    pub fn abandoned_stats(&self) -> AbandonedStats {
+        self.abandoned
+    }
+
+    /// Sends a FORWARD TSN if abandoned chunks are holding back the peer's cumulative TSN.
+    /// RFC 3758 section 3.5 C2) it is sent again after every SACK and T3-rtx expiration
+    /// until the peer acks it
+    /// RFC 8260 section 2.3: an I-FORWARD-TSN is sent instead when I-DATA is used
+    async fn send_forward_tsn(&mut self) -> Result<(), SCTPError> {
+        let chunk: Box<dyn Chunk> = if self.interleaving {
+            match i_forward_tsn(self.outstanding.iter().map(|outstanding| {
+                (
+                    &outstanding.chunk,
+                    outstanding.fragment.message_id,
+                    outstanding.abandoned,
+                )
+            })) {
+                Some(forward_tsn) => {
+                    debug!("sending i-forward tsn {}", forward_tsn.new_cumulative_tsn());
+                    Box::new(forward_tsn)
+                }
+                None => return Ok(()),
+            }
+        } else {
+            match forward_tsn(
+                self.outstanding
+                    .iter()
+                    .map(|outstanding| (&outstanding.chunk, outstanding.abandoned)),
+            ) {
+                Some(forward_tsn) => {
+                    debug!("sending forward tsn {}", forward_tsn.new_cumulative_tsn());
+                    Box::new(forward_tsn)
+                }
+                None => return Ok(()),
+            }
+        };
+        let mut packet = self.new_packet();
+        packet.add_chunk(chunk);
+        self.send_packet(&packet, self.primary_addr()).await
+    }
+
+    /// Returns the next active path after the given one,
+    /// or the given path itself if there is no other active path
+    fn alternate_path(&self, path: usize) -> usize {
+        (1..self.paths.len())
+            .map(|offset| (path + offset) % self.paths.len())
+            .find(|&alternate| self.paths[alternate].state() == PathState::Active)
+            .unwrap_or(path)
+    }
+
+    /// Returns the current rtt estimates and rto of the primary path
+    pub fn srtt_report(&self) -> SrttReport {
+        self.paths[self.primary].rto.report()
     }
 
     /// Recvs user data
-    pub async fn recv(&mut self) {
+    pub async fn recv(&mut self) -> Result<Message, SCTPError> {
         // this is the association recving function. it can recv any kind of a chunk.
         // it can recv data/error/abort/whatever. so every packet recvd should be
         // checked for the chunk type and then appropriate action should be taken.
-        //
-        // TODO abhi: when the recvr wnd is 0, drop any new incoming DATA chunk with
-        // TSN larger than the largest TSN recvd so far.
-        if let Ok(bytes) = self.stream.recv().await {
-            if let Ok(packet) = Packet::try_from(bytes) {
-                for chunk in packet.chunks {
-                    match chunk.chunk_type() {
-                        Data => {}
-                        Init => {}
-                        InitAck => {}
-                        Sack => {}
-                        Abort => {}
-                        Shutdown => {}
-                        CookieAck => {}
-                        CookieEcho => {}
-                        ShutdownComplete => {}
-                        ShutdownAck => {}
-                        Invalid => {}
+        loop {
+            if let Some(msg) = self.msg_queue.pop_front() {
+                // the ulp has consumed the msg so its space in the recv buffer is free again
+                self.recv_buffer.on_delivered(msg.data.len() as u32);
+                return Ok(msg);
+            }
+            if self.failed {
+                return Err(SCTPError::AssociationFailed);
+            }
+
+            let deadline = self
+                .paths
+                .iter()
+                .flat_map(|path| [path.heartbeat.deadline(), path.plpmtud.deadline()])
+                .flatten()
+                .min();
+            let (bytes, from) = match deadline {
+                Some(deadline) => match timeout_at(deadline, self.stream.recv_from()).await {
+                    Ok(result) => result?,
+                    Err(_) => {
+                        self.handle_heartbeat_timers().await?;
+                        self.handle_plpmtud_timers().await?;
+                        continue;
+                    }
+                },
+                None => self.stream.recv_from().await?,
+            };
+
+            if let Ok(packet) = self.parse_packet(bytes) {
+                if self.handle_packet(packet, from).await? {
+                    // data a send left buffered goes out as the peer acks what's in flight
+                    self.abandon_expired();
+                    self.transmit().await?;
+                    self.send_forward_tsn().await?;
+                }
+            }
+        }
+    }
+
+    /// Sends a heartbeat on every idle path whose heartbeat timer has expired
+    async fn handle_heartbeat_timers(&mut self) -> Result<(), SCTPError> {
+        let now = Instant::now();
+        for path in 0..self.paths.len() {
+            match self.paths[path].heartbeat.deadline() {
+                Some(deadline) if deadline <= now => {}
+                _ => continue,
+            }
+            // section 8.3: a heartbeat that isn't acked within an RTO counts as an error.
+            // the timer never expires before an RTO has passed since the last heartbeat
+            if self.paths[path].heartbeat.is_pending() {
+                self.on_timeout_error(path)?;
+            }
+            self.send_heartbeat(path).await?;
+            self.restart_heartbeat_timer(path);
+        }
+        Ok(())
+    }
+
+    /// Restarts the heartbeat timer of a path with some jitter
+    fn restart_heartbeat_timer(&mut self, path: usize) {
+        let rto = self.paths[path].rto.rto();
+        // RFC 7829 section 5.1: a potentially failed path is probed every RTO
+        if self.paths[path].state() == PathState::PotentiallyFailed {
+            self.paths[path].heartbeat.restart_after(rto);
+            return;
+        }
+        let jitter = self.rng.gen_range(-0.5..=0.5);
+        self.paths[path].heartbeat.restart(rto, jitter);
+    }
+
+    /// Sends a HEARTBEAT on the given path
+    async fn send_heartbeat(&mut self, path: usize) -> Result<(), SCTPError> {
+        let addr = self.paths[path].addr();
+        debug!("sending heartbeat to {}", addr);
+        let info = self.paths[path].heartbeat.on_send(self.rng.gen());
+        let mut packet = self.new_packet();
+        packet.add_chunk(Box::new(Heartbeat::new((&info).into())));
+        self.send_packet(&packet, addr).await
+    }
+
+    /// Runs the PLPMTUD state machine of every path whose timer has expired
+    /// and sends the probes it asks for
+    async fn handle_plpmtud_timers(&mut self) -> Result<(), SCTPError> {
+        let now = Instant::now();
+        let expired = self.paths.iter().any(|path| {
+            path.plpmtud
+                .deadline()
+                .is_some_and(|deadline| deadline <= now)
+        });
+        if !expired {
+            return Ok(());
+        }
+        // the ack of a probe may be waiting to be read while the ULP wasn't sending
+        self.handle_arrived_packets().await?;
+
+        let now = Instant::now();
+        for path in 0..self.paths.len() {
+            let pmtu = self.paths[path].pmtu();
+            let rto = self.paths[path].rto.rto();
+            let nonce = self.rng.gen();
+            let size = self.paths[path].plpmtud.on_timer(now, rto, nonce);
+            if self.paths[path].pmtu() != pmtu {
+                self.on_pmtu_change(path);
+            }
+            if let Some(size) = size {
+                self.send_probe(path, size, nonce).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Processes the packets that have arrived already without waiting for more
+    async fn handle_arrived_packets(&mut self) -> Result<(), SCTPError> {
+        // a timeout of zero still polls the socket once
+        while let Ok(result) = timeout(Duration::ZERO, self.stream.recv_from()).await {
+            let (bytes, from) = result?;
+            if let Ok(packet) = self.parse_packet(bytes) {
+                self.handle_packet(packet, from).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a HEARTBEAT padded with a PAD chunk so the IP packet is `size` bytes long
+    /// (RFC 8899 section 6.2.1.2)
+    async fn send_probe(&mut self, path: usize, size: u32, nonce: u64) {
+        let addr = self.paths[path].addr();
+        debug!("probing pmtu {} to {}", size, addr);
+        // the timestamp isn't used as probes don't take RTT samples
+        let info = HeartbeatInfo {
+            timestamp: 0,
+            nonce,
+        };
+        let mut packet = self.new_packet();
+        packet.add_chunk(Box::new(Heartbeat::new((&info).into())));
+        packet.pad_to(size as usize - ip_header_size(&addr) - UDP_HEADER_SIZE);
+        // a probe too large for the local interface is lost like any other
+        if self
+            .stream
+            .send_to(&self.encode_packet(&packet), addr)
+            .await
+            .is_err()
+        {
+            debug!("couldn't send pmtu probe of {} bytes", size);
+        }
+    }
+
+    /// Tells the congestion control of a path about its new PMTU
+    fn on_pmtu_change(&mut self, path: usize) {
+        let pmtu = self.paths[path].pmtu();
+        debug!("pmtu of {} is {}", self.paths[path].addr(), pmtu);
+        self.paths[path].cc.set_mtu(pmtu);
+    }
+
+    /// Sends a HEARTBEAT to the primary path right away on behalf of the ULP
+    pub async fn request_heartbeat(&mut self) -> Result<(), SCTPError> {
+        self.send_heartbeat(self.primary).await
+    }
+
+    /// Enables or disables heartbeats on every path and changes the interval between them
+    pub fn change_heartbeat(&mut self, enabled: bool, interval: Duration) {
+        for path in 0..self.paths.len() {
+            self.paths[path].heartbeat.set(enabled, interval);
+            self.restart_heartbeat_timer(path);
+        }
+    }
+
+    /// Takes an RTT sample from a HEARTBEAT ACK that matches the last heartbeat
+    /// sent on one of the paths
+    fn handle_heartbeat_ack(&mut self, ack: HeartbeatAck) {
+        let info = match HeartbeatInfo::try_from(ack.info()) {
+            Ok(info) => info,
+            Err(_) => return,
+        };
+        for path in 0..self.paths.len() {
+            if self.paths[path].plpmtud.on_ack(info.nonce, Instant::now()) {
+                self.on_pmtu_change(path);
+                return;
+            }
+            if let Some(rtt) = self.paths[path].heartbeat.on_ack(&info) {
+                debug!("heartbeat rtt {:?} to {}", rtt, self.paths[path].addr());
+                self.paths[path].rto.on_measurement(rtt);
+                self.on_peer_reachable(path);
+                return;
+            }
+        }
+    }
+
+    /// Counts a T3-rtx expiration or an unacked heartbeat against the path
+    /// and the association. Fails the association once it has seen
+    /// more than Association.Max.Retrans errors in a row.
+    fn on_timeout_error(&mut self, path: usize) -> Result<(), SCTPError> {
+        if let Some(state) = self.paths[path].on_error() {
+            self.on_path_state_change(path, state);
+        }
+
+        self.error_count += 1;
+        debug!("association error count {}", self.error_count);
+        if self.error_count > self.config.association_max_retrans {
+            info!("association failed");
+            self.failed = true;
+            self.notifications
+                .push_back(Notification::CommunicationLost);
+            return Err(SCTPError::AssociationFailed);
+        }
+        Ok(())
+    }
+
+    /// Clears the error counts after the peer acked DATA or a HEARTBEAT sent on a path
+    fn on_peer_reachable(&mut self, path: usize) {
+        self.error_count = 0;
+        if let Some(state) = self.paths[path].on_ack() {
+            self.on_path_state_change(path, state);
+        }
+    }
+
+    /// Tells the ULP about the new state of a path and fails over to
+    /// or back from another path if needed
+    fn on_path_state_change(&mut self, path: usize, state: PathState) {
+        let addr = self.paths[path].addr();
+        debug!("path {} is {:?}", addr, state);
+        self.notifications
+            .push_back(Notification::NetworkStatusChange { addr, state });
+        self.update_primary();
+    }
+
+    /// Picks the path new DATA is sent on from the current state of the paths
+    fn update_primary(&mut self) {
+        let primary = select_primary(
+            &self.paths,
+            self.primary,
+            self.preferred_primary,
+            self.config.failback,
+        );
+        self.change_primary(primary);
+    }
+
+    /// Sends new DATA on the given path from now on
+    fn change_primary(&mut self, primary: usize) {
+        if primary == self.primary {
+            return;
+        }
+        self.primary = primary;
+        info!("primary path is now {}", self.primary_addr());
+        self.notifications.push_back(Notification::PrimaryChanged {
+            addr: self.primary_addr(),
+        });
+    }
+
+    /// Sets the destination addr of the peer new DATA is sent to
+    pub fn set_primary(&mut self, addr: SocketAddr) -> Result<(), SCTPError> {
+        let path = self
+            .paths
+            .iter()
+            .position(|path| path.addr() == addr)
+            .ok_or(SCTPError::InvalidRemoteAddress)?;
+        self.preferred_primary = path;
+        // the ULP's choice is honored right away unless the path is down
+        if self.paths[path].state() == PathState::Active {
+            self.change_primary(path);
+        } else {
+            self.update_primary();
+        }
+        Ok(())
+    }
+
+    /// Returns the destination addr of the peer new DATA is sent to
+    pub fn primary(&self) -> SocketAddr {
+        self.primary_addr()
+    }
+
+    /// Changes the error thresholds of the association and its paths
+    pub fn set_failure_threshold(&mut self, association_max_retrans: u32, path_max_retrans: u32) {
+        self.config.association_max_retrans = association_max_retrans;
+        self.config.path_max_retrans = path_max_retrans;
+        let thresholds = PathThresholds::from(&self.config);
+        for path in self.paths.iter_mut() {
+            path.set_thresholds(thresholds);
+        }
+    }
+
+    /// Returns the next pending notification for the ULP
+    pub fn poll_notification(&mut self) -> Option<Notification> {
+        self.notifications.pop_front()
+    }
+
+    /// Returns the transport addrs of the peer
+    pub fn peer_addrs(&self) -> Vec<SocketAddr> {
+        self.paths.iter().map(|path| path.addr()).collect()
+    }
+
+    /// Returns our local addrs
+    pub fn local_addrs(&self) -> &[SocketAddr] {
+        &self.local_addrs
+    }
+
+    /// Returns true if I-DATA is used and msgs on different streams can be interleaved
+    pub(crate) fn interleaving(&self) -> bool {
+        self.scheduler.interleaving()
+    }
+
+    /// Returns true if the peer can be asked to change the addrs of the association.
+    /// RFC 5061 section 4.1.1: ASCONFs must be authenticated so AUTH must be
+    /// negotiated along with the ASCONF extension
+    fn asconf_supported(&self) -> bool {
+        self.auth_negotiated()
+            && ASCONF_CHUNK_TYPES.iter().all(|&chunk_type| {
+                self.peer_extensions.contains(&chunk_type)
+                    && self.auth.peer_requires_auth(chunk_type)
+            })
+    }
+
+    /// Returns true if AUTH (RFC 4895) has been negotiated with the peer
+    fn auth_negotiated(&self) -> bool {
+        self.auth.is_negotiated()
+    }
+
+    /// Adds or replaces an endpoint pair shared key used to authenticate chunks
+    pub fn add_shared_key(&mut self, key_id: u16, key: Vec<u8>) {
+        self.auth.add_key(key_id, key);
+    }
+
+    /// Authenticates the chunks we send with the given shared key from now on
+    pub fn set_active_key(&mut self, key_id: u16) -> Result<(), SCTPError> {
+        self.auth.set_active_key(key_id)
+    }
+
+    /// Deletes a shared key that isn't the active one
+    pub fn delete_shared_key(&mut self, key_id: u16) -> Result<(), SCTPError> {
+        self.auth.delete_key(key_id)
+    }
+
+    /// Binds one more local addr and asks the peer to add it to the association
+    pub async fn add_local_addr(&mut self, addr: SocketAddr) -> Result<(), SCTPError> {
+        if !self.asconf_supported() {
+            return Err(SCTPError::AsconfNotSupported);
+        }
+        // an association only has a single local port
+        if addr.port() != self.local_addrs[0].port() || self.local_addrs.contains(&addr) {
+            return Err(SCTPError::InvalidLocalAddress);
+        }
+
+        // the new addr must be able to recv as soon as the peer knows about it
+        self.stream.add(addr).await?;
+        match self.reconfigure(&[(AsconfOp::AddIp, addr.ip())]).await {
+            Ok(()) => {
+                self.local_addrs.push(addr);
+                Ok(())
+            }
+            Err(e) => {
+                self.stream.remove(addr);
+                Err(e)
+            }
+        }
+    }
+
+    /// Asks the peer to remove one of our local addrs from the association
+    pub async fn delete_local_addr(&mut self, addr: SocketAddr) -> Result<(), SCTPError> {
+        if !self.asconf_supported() {
+            return Err(SCTPError::AsconfNotSupported);
+        }
+        // RFC 5061 section 5.1.5: the last addr can't be deleted
+        if !self.local_addrs.contains(&addr) || self.local_addrs.len() == 1 {
+            return Err(SCTPError::InvalidLocalAddress);
+        }
+
+        self.reconfigure(&[(AsconfOp::DeleteIp, addr.ip())]).await?;
+        self.local_addrs.retain(|local_addr| *local_addr != addr);
+        self.stream.remove(addr);
+        Ok(())
+    }
+
+    /// Asks the peer to send its DATA to the given local addr
+    pub async fn set_peer_primary(&mut self, addr: SocketAddr) -> Result<(), SCTPError> {
+        if !self.asconf_supported() {
+            return Err(SCTPError::AsconfNotSupported);
+        }
+        if !self.local_addrs.contains(&addr) {
+            return Err(SCTPError::InvalidLocalAddress);
+        }
+
+        self.reconfigure(&[(AsconfOp::SetPrimary, addr.ip())]).await
+    }
+
+    /// Sends an ASCONF carrying the given operations and waits for it to be acked.
+    /// The ASCONF is retransmitted to an alternate path like DATA (RFC 5061 section 5.1).
+    async fn reconfigure(&mut self, ops: &[(AsconfOp, IpAddr)]) -> Result<(), SCTPError> {
+        // the ASCONF must be sent from an addr that isn't being deleted
+        let from = self
+            .local_addrs
+            .iter()
+            .map(|addr| addr.ip())
+            .find(|ip| !ops.contains(&(AsconfOp::DeleteIp, *ip)))
+            .ok_or(SCTPError::InvalidLocalAddress)?;
+        let asconf = self
+            .asconf
+            .send(from, ops)
+            .ok_or(SCTPError::AsconfRefused)?;
+        let mut packet = self.new_packet();
+        packet.add_chunk(Box::new(asconf));
+
+        let mut path = self.primary;
+        self.send_packet(&packet, self.paths[path].addr()).await?;
+        loop {
+            match timeout(self.paths[path].rto.rto(), self.stream.recv_from()).await {
+                Ok(result) => {
+                    let (bytes, from) = result?;
+                    if let Ok(packet) = self.parse_packet(bytes) {
+                        self.handle_packet(packet, from).await?;
+                    }
+                    if let Some(results) = self.asconf.take_results() {
+                        return match results.iter().find(|(_, error)| error.is_some()) {
+                            Some((request, error)) => {
+                                debug!("peer refused {:?} with cause {:?}", request, error);
+                                Err(SCTPError::AsconfRefused)
+                            }
+                            None => Ok(()),
+                        };
+                    }
+                }
+                _ => {
+                    self.on_timeout_error(path)?;
+                    self.paths[path].rto.backoff();
+                    path = self.alternate_path(path);
+                    self.send_packet(&packet, self.paths[path].addr()).await?;
+                }
+            }
+        }
+    }
+
+    /// Processes an ASCONF of the peer and acks it (RFC 5061 section 5.2)
+    async fn handle_asconf(&mut self, asconf: Asconf, from: SocketAddr) -> Result<(), SCTPError> {
+        // RFC 5061 section 4.1.1: ASCONFs that aren't authenticated are silently discarded
+        if !self.auth_negotiated() {
+            debug!("discarding unauthenticated asconf");
+            return Ok(());
+        }
+        // RFC 5061 section 4.1.1: the address parameter is mandatory
+        if asconf.addr().is_none() {
+            debug!("discarding asconf without an address parameter");
+            return Ok(());
+        }
+
+        let ack = match self.peer_asconf.on_asconf(&asconf) {
+            AsconfVerdict::New => {
+                let responses = asconf
+                    .requests()
+                    .iter()
+                    .map(|request| self.apply_asconf_request(request, from))
+                    .collect();
+                let ack = AsconfAck::new(asconf.serial_no(), responses);
+                self.peer_asconf.on_processed(ack.clone());
+                ack
+            }
+            AsconfVerdict::Retransmitted(ack) => ack,
+            AsconfVerdict::Stale => {
+                debug!("discarding asconf with serial no {}", asconf.serial_no());
+                return Ok(());
+            }
+        };
+
+        // the ASCONF-ACK goes back to the addr the ASCONF came from
+        let mut packet = self.new_packet();
+        packet.add_chunk(Box::new(ack));
+        self.send_packet(&packet, from).await
+    }
+
+    /// Applies a single request of an ASCONF of the peer
+    fn apply_asconf_request(
+        &mut self,
+        request: &AsconfRequest,
+        from: SocketAddr,
+    ) -> AsconfResponse {
+        // RFC 5061 section 4.2.1: a wildcard addr stands for the source addr of the packet
+        let addr = if request.addr.is_unspecified() {
+            from
+        } else {
+            SocketAddr::new(request.addr, from.port())
+        };
+        let path = self.paths.iter().position(|path| path.addr() == addr);
+
+        let error = match request.op {
+            AsconfOp::AddIp => {
+                if path.is_none() {
+                    self.paths.push(Path::new(addr, &self.config));
+                    self.notifications
+                        .push_back(Notification::PeerAddrAdded { addr });
+                }
+                None
+            }
+            AsconfOp::DeleteIp => match path {
+                // deleting an addr that isn't part of the association succeeds
+                None => None,
+                Some(_) if self.paths.len() == 1 => Some(DELETE_LAST_ADDR_CAUSE),
+                Some(_) if addr == from => Some(DELETE_SOURCE_ADDR_CAUSE),
+                Some(path) => {
+                    self.remove_path(path);
+                    None
+                }
+            },
+            AsconfOp::SetPrimary => match self.set_primary(addr) {
+                Ok(()) => None,
+                Err(_) => Some(UNRESOLVABLE_ADDR_CAUSE),
+            },
+        };
+
+        AsconfResponse {
+            correlation_id: request.correlation_id,
+            error,
+        }
+    }
+
+    /// Returns true if the peer supports stream reconfiguration (RFC 6525 section 4)
+    fn reconfig_supported(&self) -> bool {
+        self.peer_extensions.contains(&RECONFIG_CHUNK_TYPE)
+    }
+
+    /// Resets the seq nos of the given outbound streams, all of them if none are given.
+    /// The DATA already sent on the streams is acked before they are reset.
+    pub async fn reset_streams(&mut self, stream_ids: &[u16]) -> Result<(), SCTPError> {
+        if !self.reconfig_supported() {
+            return Err(SCTPError::ReconfigNotSupported);
+        }
+        if stream_ids
+            .iter()
+            .any(|&stream_id| stream_id as usize >= self.outbound_ssns.len())
+        {
+            return Err(SCTPError::InvalidStreamId);
+        }
+
+        self.flush().await?;
+        self.reconfigure_streams(vec![ReconfigRequest::OutgoingSsnReset {
+            seq_no: 0,
+            response_seq_no: self.peer_reconfig.last_seq_no(),
+            last_tsn: self.tsn,
+            streams: stream_ids.to_vec(),
+        }])
+        .await
+    }
+
+    /// Asks the peer to reset the seq nos of the given inbound streams,
+    /// all of them if none are given
+    pub async fn reset_incoming_streams(&mut self, stream_ids: &[u16]) -> Result<(), SCTPError> {
+        if !self.reconfig_supported() {
+            return Err(SCTPError::ReconfigNotSupported);
+        }
+        if stream_ids
+            .iter()
+            .any(|&stream_id| !self.inbound_streams.is_valid_stream(stream_id))
+        {
+            return Err(SCTPError::InvalidStreamId);
+        }
+
+        self.reconfigure_streams(vec![ReconfigRequest::IncomingSsnReset {
+            seq_no: 0,
+            streams: stream_ids.to_vec(),
+        }])
+        .await
+    }
+
+    /// Resets the seq nos of all the streams and the TSNs in both directions.
+    /// The DATA already sent is acked before the association is reset.
+    pub async fn reset_association(&mut self) -> Result<(), SCTPError> {
+        if !self.reconfig_supported() {
+            return Err(SCTPError::ReconfigNotSupported);
+        }
+
+        self.flush().await?;
+        self.reconfigure_streams(vec![ReconfigRequest::SsnTsnReset { seq_no: 0 }])
+            .await
+    }
+
+    /// Adds outbound streams and asks the peer to add inbound streams
+    pub async fn add_streams(
+        &mut self,
+        num_outbound: u16,
+        num_inbound: u16,
+    ) -> Result<(), SCTPError> {
+        if !self.reconfig_supported() {
+            return Err(SCTPError::ReconfigNotSupported);
+        }
+        if self.outbound_ssns.len() + num_outbound as usize > u16::MAX as usize
+            || self.inbound_streams.num_streams() as usize + num_inbound as usize
+                > u16::MAX as usize
+        {
+            return Err(SCTPError::InvalidStreamId);
+        }
+
+        let mut requests = vec![];
+        if num_outbound > 0 {
+            requests.push(ReconfigRequest::AddOutgoingStreams {
+                seq_no: 0,
+                num_streams: num_outbound,
+            });
+        }
+        if num_inbound > 0 {
+            requests.push(ReconfigRequest::AddIncomingStreams {
+                seq_no: 0,
+                num_streams: num_inbound,
+            });
+        }
+        if requests.is_empty() {
+            return Ok(());
+        }
+        self.reconfigure_streams(requests).await
+    }
+
+    /// Sends a RE-CONFIG carrying the given requests and waits for all of them to complete.
+    /// The RE-CONFIG is retransmitted to an alternate path like DATA,
+    /// or to the same path if the peer is still working on it (RFC 6525 section 5.1.1).
+    async fn reconfigure_streams(
+        &mut self,
+        requests: Vec<ReconfigRequest>,
+    ) -> Result<(), SCTPError> {
+        let reconfig = self
+            .reconfig
+            .send(requests)
+            .ok_or(SCTPError::ReconfigRefused)?;
+        let mut packet = self.new_packet();
+        packet.add_chunk(Box::new(reconfig));
+
+        let mut path = self.primary;
+        self.send_packet(&packet, self.paths[path].addr()).await?;
+        loop {
+            match timeout(self.paths[path].rto.rto(), self.stream.recv_from()).await {
+                Ok(result) => {
+                    let (bytes, from) = result?;
+                    if let Ok(packet) = self.parse_packet(bytes) {
+                        self.handle_packet(packet, from).await?;
+                    }
+                    if self.reconfig.outstanding().is_empty() {
+                        let results = self.reconfig.take_results();
+                        return match results
+                            .iter()
+                            .find(|(_, response)| !response.result.is_success())
+                        {
+                            Some((request, response)) => {
+                                debug!("peer refused {:?} with {:?}", request, response.result);
+                                Err(SCTPError::ReconfigRefused)
+                            }
+                            None => Ok(()),
+                        };
+                    }
+                }
+                _ => {
+                    if !self.reconfig.take_in_progress() {
+                        self.on_timeout_error(path)?;
+                        self.paths[path].rto.backoff();
+                        path = self.alternate_path(path);
                     }
+                    let mut packet = self.new_packet();
+                    packet.add_chunk(Box::new(self.reconfig.retransmission()));
+                    self.send_packet(&packet, self.paths[path].addr()).await?;
                 }
             }
         }
     }
 
+    /// Processes the responses and the requests in a RE-CONFIG of the peer
+    /// and answers the requests (RFC 6525 section 5.2)
+    async fn handle_reconfig(
+        &mut self,
+        reconfig: ReConfig,
+        from: SocketAddr,
+    ) -> Result<(), SCTPError> {
+        for response in reconfig.responses() {
+            if let Some(request) = self.reconfig.on_response(response) {
+                self.on_reconfig_response(&request, response);
+            }
+        }
+
+        // requests of ours the peer's requests call for
+        let mut new_requests = vec![];
+        let mut responses = vec![];
+        let mut retransmitted = false;
+        for request in reconfig.requests() {
+            let response = match self.peer_reconfig.on_request(request) {
+                ReconfigVerdict::New => {
+                    let response = self.apply_reconfig_request(request, &mut new_requests);
+                    self.peer_reconfig.on_processed(response.clone());
+                    response
+                }
+                ReconfigVerdict::Retransmitted(response) => {
+                    retransmitted = true;
+                    response
+                }
+                ReconfigVerdict::BadSeqNo => ReconfigResponse {
+                    seq_no: request.seq_no(),
+                    result: ReconfigResult::ErrorBadSequenceNumber,
+                    next_tsns: None,
+                },
+            };
+            // section 5.2.3: an Incoming SSN Reset Request is answered
+            // by the Outgoing SSN Reset Request it triggers
+            let answered_by_request = matches!(request, ReconfigRequest::IncomingSsnReset { .. })
+                && response.result.is_success();
+            if !answered_by_request {
+                responses.push(response);
+            }
+        }
+
+        let requests = if !new_requests.is_empty() {
+            self.reconfig.send(new_requests);
+            self.reconfig.outstanding().to_vec()
+        } else if retransmitted {
+            // the requests the peer's retransmitted requests called for may have been lost too
+            self.reconfig.outstanding().to_vec()
+        } else {
+            vec![]
+        };
+        if responses.is_empty() && requests.is_empty() {
+            return Ok(());
+        }
+        let mut packet = self.new_packet();
+        packet.add_chunk(Box::new(ReConfig::new(responses, requests)));
+        self.send_packet(&packet, from).await
+    }
+
+    /// Applies a single request of a RE-CONFIG of the peer and returns the response to it.
+    /// Requests of ours that the request calls for are added to `new_requests`.
+    fn apply_reconfig_request(
+        &mut self,
+        request: &ReconfigRequest,
+        new_requests: &mut Vec<ReconfigRequest>,
+    ) -> ReconfigResponse {
+        // only one RE-CONFIG of ours can be outstanding at a time
+        let busy = !self.reconfig.outstanding().is_empty();
+        let mut next_tsns = None;
+
+        let result =
+            match request {
+                ReconfigRequest::OutgoingSsnReset {
+                    response_seq_no,
+                    last_tsn,
+                    streams,
+                    ..
+                } => {
+                    self.complete_incoming_reset(*response_seq_no);
+                    if streams
+                        .iter()
+                        .any(|&stream_id| !self.inbound_streams.is_valid_stream(stream_id))
+                    {
+                        ReconfigResult::Denied
+                    } else if !tsn_lte(*last_tsn, self.recv_buffer.cumulative_tsn()) {
+                        // section 5.2.2: the streams are reset once all the DATA
+                        // sent on them has arrived
+                        ReconfigResult::InProgress
+                    } else {
+                        self.inbound_streams.reset(streams);
+                        self.notifications.push_back(Notification::StreamsReset {
+                            stream_ids: streams.clone(),
+                            incoming: true,
+                        });
+                        ReconfigResult::SuccessPerformed
+                    }
+                }
+                ReconfigRequest::IncomingSsnReset { seq_no, streams } => {
+                    if busy {
+                        ReconfigResult::ErrorRequestAlreadyInProgress
+                    } else if streams
+                        .iter()
+                        .any(|&stream_id| stream_id as usize >= self.outbound_ssns.len())
+                    {
+                        ReconfigResult::Denied
+                    } else if self.send_queue.iter().any(|queued| {
+                        streams.is_empty() || streams.contains(&queued.chunk.stream_id())
+                    }) {
+                        // the queued msgs have to be sent with the seq nos from before the reset
+                        ReconfigResult::InProgress
+                    } else {
+                        new_requests.push(ReconfigRequest::OutgoingSsnReset {
+                            seq_no: 0,
+                            response_seq_no: *seq_no,
+                            last_tsn: self.tsn,
+                            streams: streams.clone(),
+                        });
+                        ReconfigResult::SuccessPerformed
+                    }
+                }
+                ReconfigRequest::SsnTsnReset { .. } => {
+                    if !self.send_queue.is_empty() || !self.outstanding.is_empty() {
+                        // section 5.2.4: our DATA must be acked before the TSNs are reset
+                        ReconfigResult::InProgress
+                    } else {
+                        // section 5.2.4: both TSNs move 2^31 ahead so that
+                        // DATA from before the reset can't be mistaken for new DATA
+                        let next_tsn = self.tsn.wrapping_add(1).wrapping_add(1 << 31);
+                        let peer_next_tsn = self
+                            .recv_buffer
+                            .cumulative_tsn()
+                            .wrapping_add(1)
+                            .wrapping_add(1 << 31);
+                        self.reset_tsns(next_tsn, peer_next_tsn);
+                        next_tsns = Some((next_tsn, peer_next_tsn));
+                        ReconfigResult::SuccessPerformed
+                    }
+                }
+                ReconfigRequest::AddOutgoingStreams { num_streams, .. } => {
+                    if self.inbound_streams.num_streams() as usize + *num_streams as usize
+                        > u16::MAX as usize
+                    {
+                        ReconfigResult::Denied
+                    } else {
+                        self.inbound_streams.add_streams(*num_streams);
+                        self.notify_streams_changed();
+                        ReconfigResult::SuccessPerformed
+                    }
+                }
+                ReconfigRequest::AddIncomingStreams { num_streams, .. } => {
+                    if busy {
+                        ReconfigResult::ErrorRequestAlreadyInProgress
+                    } else if self.outbound_ssns.len() + *num_streams as usize > u16::MAX as usize {
+                        ReconfigResult::Denied
+                    } else {
+                        new_requests.push(ReconfigRequest::AddOutgoingStreams {
+                            seq_no: 0,
+                            num_streams: *num_streams,
+                        });
+                        ReconfigResult::SuccessPerformed
+                    }
+                }
+            };
+
+        ReconfigResponse {
+            seq_no: request.seq_no(),
+            result,
+            next_tsns,
+        }
+    }
+
+    /// Completes our outstanding Incoming SSN Reset Request with the given seq no.
+    /// section 5.2.2: the Outgoing SSN Reset Request of the peer answers it
+    fn complete_incoming_reset(&mut self, seq_no: u32) {
+        let answered = self.reconfig.outstanding().iter().any(|request| {
+            matches!(request, ReconfigRequest::IncomingSsnReset { .. })
+                && request.seq_no() == seq_no
+        });
+        if answered {
+            self.reconfig.on_response(&ReconfigResponse {
+                seq_no,
+                result: ReconfigResult::SuccessPerformed,
+                next_tsns: None,
+            });
+        }
+    }
+
+    /// Applies the effects of a request of ours the peer has answered
+    fn on_reconfig_response(&mut self, request: &ReconfigRequest, response: &ReconfigResponse) {
+        if !response.result.is_success() {
+            debug!("{:?} failed with {:?}", request, response.result);
+            return;
+        }
+
+        match request {
+            ReconfigRequest::OutgoingSsnReset { streams, .. } => {
+                for stream_id in 0..self.outbound_ssns.len() {
+                    if streams.is_empty() || streams.contains(&(stream_id as u16)) {
+                        self.outbound_ssns[stream_id] = 0;
+                        self.unordered_mids[stream_id] = 0;
+                    }
+                }
+                self.notifications.push_back(Notification::StreamsReset {
+                    stream_ids: streams.clone(),
+                    incoming: false,
+                });
+            }
+            ReconfigRequest::SsnTsnReset { .. } => {
+                // the peer's next TSN is the one it expects from us and the other way around
+                if let Some((peer_next_tsn, next_tsn)) = response.next_tsns {
+                    self.reset_tsns(next_tsn, peer_next_tsn);
+                }
+            }
+            ReconfigRequest::AddOutgoingStreams { num_streams, .. } => {
+                let num_outbound = self.outbound_ssns.len() + *num_streams as usize;
+                self.outbound_ssns.resize(num_outbound, 0);
+                self.unordered_mids.resize(num_outbound, 0);
+                self.scheduler.add_streams(*num_streams);
+                self.notify_streams_changed();
+            }
+            // the peer carries these out with requests of its own
+            ReconfigRequest::IncomingSsnReset { .. }
+            | ReconfigRequest::AddIncomingStreams { .. } => {}
+        }
+    }
+
+    /// Resets the TSNs in both directions along with the seq nos of all the streams
+    /// given the next TSN we send and the next TSN we expect from the peer
+    fn reset_tsns(&mut self, next_tsn: u32, peer_next_tsn: u32) {
+        self.tsn = next_tsn.wrapping_sub(1);
+        self.recv_buffer.set_initial_tsn(peer_next_tsn);
+        for ssn in self
+            .outbound_ssns
+            .iter_mut()
+            .chain(self.unordered_mids.iter_mut())
+        {
+            *ssn = 0;
+        }
+        self.inbound_streams.reset(&[]);
+        self.notifications.push_back(Notification::AssociationReset);
+    }
+
+    /// Tells the ULP about the adaptation layer of the peer once the association is up
+    fn notify_adaptation(&mut self) {
+        if let Some(code_point) = self.peer_adaptation_code_point {
+            self.notifications
+                .push_back(Notification::AdaptationIndication { code_point });
+        }
+    }
+
+    fn notify_streams_changed(&mut self) {
+        self.notifications.push_back(Notification::StreamsChanged {
+            num_inbound: self.inbound_streams.num_streams(),
+            num_outbound: self.outbound_ssns.len() as u16,
+        });
+    }
+
+    /// Removes a path deleted by the peer.
+    /// Whatever was using it moves over to the primary path.
+    fn remove_path(&mut self, path: usize) {
+        let addr = self.paths.remove(path).addr();
+        self.notifications
+            .push_back(Notification::PeerAddrRemoved { addr });
+
+        let primary_removed = self.primary == path;
+        let reindex = |idx: usize| match idx {
+            idx if idx == path => 0,
+            idx if idx > path => idx - 1,
+            idx => idx,
+        };
+        self.primary = reindex(self.primary);
+        self.preferred_primary = reindex(self.preferred_primary);
+        for outstanding in self.outstanding.iter_mut() {
+            outstanding.path = if outstanding.path == path {
+                self.primary
+            } else {
+                reindex(outstanding.path)
+            };
+        }
+
+        if primary_removed {
+            self.notifications.push_back(Notification::PrimaryChanged {
+                addr: self.primary_addr(),
+            });
+        }
+        self.update_primary();
+    }
+
+    /// Processes every chunk in a packet recvd from the given addr of the peer.
+    /// Returns true if the packet carried a SACK.
+    async fn handle_packet(
+        &mut self,
+        mut packet: Packet,
+        from: SocketAddr,
+    ) -> Result<bool, SCTPError> {
+        let mut data_recvd = false;
+        let mut sack_recvd = false;
+        let mut errors: Vec<Box<dyn Cause>> = vec![];
+        // the DATA in a datagram a congested router marked is echoed back to the peer
+        let ce_marked = self.ecn && self.stream.ce_marked();
+        for chunk in packet.unrecognized.drain(..) {
+            debug!("recvd chunk of unknown type {}", chunk[0]);
+            errors.push(Box::new(UnrecognizedChunkType::new(chunk)));
+        }
+
+        // RFC 4895 section 6.3: only the chunks after the first AUTH chunk are authenticated.
+        // the AUTH chunk and the chunks after it are discarded if its HMAC doesn't match
+        let auth_idx = packet
+            .chunks
+            .iter()
+            .position(|chunk| chunk.chunk_type() == ChunkType::Auth);
+        let authenticated_from = match auth_idx {
+            Some(idx) => {
+                let auth = Auth::from(&packet.chunks[idx].get_bytes()[..]);
+                if self.auth.verify(&auth, packet.bytes_after(idx)) {
+                    Some(idx + 1)
+                } else {
+                    debug!("discarding chunks with an invalid hmac");
+                    packet.chunks.truncate(idx);
+                    None
+                }
+            }
+            None => None,
+        };
+
+        for (idx, chunk) in packet.chunks.into_iter().enumerate() {
+            let authenticated = authenticated_from.is_some_and(|from| idx >= from);
+            if !authenticated && self.auth.requires_auth(u8::from(&chunk.chunk_type())) {
+                debug!("discarding unauthenticated {:?} chunk", chunk.chunk_type());
+                continue;
+            }
+
+            match chunk.chunk_type() {
+                ChunkType::Data => {
+                    let data = Data::from(chunk.get_bytes());
+                    if ce_marked {
+                        self.ecn_receiver.on_ce(data.tsn());
+                    }
+                    if let Some(error) = self.handle_data(data) {
+                        errors.push(Box::new(error));
+                    }
+                    data_recvd = true;
+                }
+                ChunkType::Init => {}
+                ChunkType::InitAck => {}
+                ChunkType::Sack => {
+                    let sack = Sack::from(chunk.get_bytes());
+                    self.handle_sack(sack.cumulative_tsn_ack, sack.a_rwnd, &[]);
+                    sack_recvd = true;
+                }
+                ChunkType::NrSack => {
+                    let sack = NrSack::from(&chunk.get_bytes()[..]);
+                    self.handle_sack(
+                        sack.cumulative_tsn_ack(),
+                        sack.a_rwnd(),
+                        sack.nr_gap_ack_blocks(),
+                    );
+                    sack_recvd = true;
+                }
+                ChunkType::Heartbeat => {
+                    // section 8.3: echo the heartbeat info back as is
+                    // to the addr the heartbeat came from
+                    let heartbeat = Heartbeat::from(&chunk.get_bytes()[..]);
+                    let mut packet = self.new_packet();
+                    packet.add_chunk(Box::new(HeartbeatAck::new(heartbeat.info().to_vec())));
+                    self.send_packet(&packet, from).await?;
+                }
+                ChunkType::HeartbeatAck => {
+                    self.handle_heartbeat_ack(HeartbeatAck::from(&chunk.get_bytes()[..]))
+                }
+                ChunkType::Abort => {}
+                ChunkType::Shutdown => {}
+                ChunkType::Error => debug!("recvd error: {:?}", chunk),
+                ChunkType::CookieAck => {}
+                ChunkType::CookieEcho => {}
+                ChunkType::Ecne => {
+                    self.handle_ecne(Ecne::from(&chunk.get_bytes()[..]), from)
+                        .await?
+                }
+                ChunkType::Cwr => self.ecn_receiver.on_cwr(&Cwr::from(&chunk.get_bytes()[..])),
+                ChunkType::ShutdownComplete => {}
+                ChunkType::ShutdownAck => {}
+                ChunkType::Asconf => {
+                    self.handle_asconf(Asconf::from(&chunk.get_bytes()[..]), from)
+                        .await?
+                }
+                ChunkType::AsconfAck => {
+                    if !self.asconf.on_ack(&AsconfAck::from(&chunk.get_bytes()[..])) {
+                        debug!("discarding asconf-ack that doesn't match the outstanding asconf");
+                    }
+                }
+                ChunkType::ForwardTsn => {
+                    self.handle_forward_tsn(ForwardTsn::from(&chunk.get_bytes()[..]));
+                    // RFC 3758 section 3.6: a FORWARD TSN is acked right away like DATA
+                    data_recvd = true;
+                }
+                ChunkType::ReConfig => {
+                    self.handle_reconfig(ReConfig::from(&chunk.get_bytes()[..]), from)
+                        .await?
+                }
+                ChunkType::IData => {
+                    let data = IData::from(&chunk.get_bytes()[..]);
+                    if ce_marked {
+                        self.ecn_receiver.on_ce(data.tsn());
+                    }
+                    if let Some(error) = self.handle_idata(data) {
+                        errors.push(Box::new(error));
+                    }
+                    data_recvd = true;
+                }
+                ChunkType::IForwardTsn => {
+                    self.handle_i_forward_tsn(IForwardTsn::from(&chunk.get_bytes()[..]));
+                    data_recvd = true;
+                }
+                ChunkType::Auth => {}
+                // RFC 4820 section 3: the padding is ignored
+                ChunkType::Pad => {}
+                ChunkType::Invalid => {}
+            }
+        }
+
+        // every packet carrying DATA is acked right away.
+        // section 6.4: the SACK goes to the addr the DATA came from
+        if data_recvd {
+            self.send_sack(errors, from).await?;
+        } else if !errors.is_empty() {
+            let mut packet = self.new_packet();
+            packet.add_chunk(Box::new(Error::new(errors)));
+            self.send_packet(&packet, from).await?;
+        }
+
+        Ok(sack_recvd)
+    }
+
+    /// Reduces the cwnd of the path the marked DATA was sent on, at most once per window
+    /// of data, and tells the peer with a CWR (RFC 4960 appendix A)
+    async fn handle_ecne(&mut self, ecne: Ecne, from: SocketAddr) -> Result<(), SCTPError> {
+        let (cwr, reduce) = self.ecn_sender.on_ecne(&ecne, self.tsn);
+        if reduce {
+            let path = self
+                .outstanding
+                .iter()
+                .find(|outstanding| outstanding.chunk.tsn() == ecne.lowest_tsn())
+                .map_or(self.primary, |outstanding| outstanding.path);
+            debug!("congestion experienced on path {}", path);
+            let flight_size = self.path_flight_size(path);
+            self.paths[path].cc.on_loss(flight_size);
+        }
+        let mut packet = self.new_packet();
+        packet.add_chunk(Box::new(cwr));
+        self.send_packet(&packet, from).await
+    }
+
+    /// Buffers the payload of a DATA chunk if there is room for it
+    /// and reassembles the msg it belongs to.
+    /// Returns an error cause to report if the chunk was sent on an invalid stream.
+    fn handle_data(&mut self, data: Data) -> Option<InvalidStreamId> {
+        if !self.inbound_streams.is_valid_stream(data.stream_id()) {
+            // section 6.5: the chunk is acked but its payload is discarded
+            // and the peer is told about the invalid stream
+            return match self.recv_buffer.on_data(data.tsn(), 0) {
+                DataVerdict::Accepted => Some(InvalidStreamId::new(data.stream_id())),
+                _ => None,
+            };
+        }
+
+        match self
+            .recv_buffer
+            .on_data(data.tsn(), data.payload_len() as u32)
+        {
+            DataVerdict::Accepted => match self.reassembly.push(data) {
+                Reassembled::Complete(msg) => {
+                    let msgs = self.inbound_streams.push(msg);
+                    self.deliver(msgs);
+                }
+                Reassembled::Discarded(len) => {
+                    debug!("discarding msg larger than the max msg size");
+                    self.recv_buffer.on_delivered(len as u32);
+                }
+                Reassembled::Pending => {}
+            },
+            verdict => debug!("{:?} data chunk with tsn {}", verdict, data.tsn()),
+        }
+        None
+    }
+
+    /// Buffers the payload of an I-DATA chunk if there is room for it
+    /// and reassembles the msg it belongs to, like `handle_data`
+    fn handle_idata(&mut self, data: IData) -> Option<InvalidStreamId> {
+        if !self.inbound_streams.is_valid_stream(data.stream_id()) {
+            return match self.recv_buffer.on_data(data.tsn(), 0) {
+                DataVerdict::Accepted => Some(InvalidStreamId::new(data.stream_id())),
+                _ => None,
+            };
+        }
+
+        match self
+            .recv_buffer
+            .on_data(data.tsn(), data.payload_len() as u32)
+        {
+            DataVerdict::Accepted => match self.interleaved_reassembly.push(data) {
+                Reassembled::Complete(msg) => {
+                    let msgs = self.inbound_streams.push(msg);
+                    self.deliver(msgs);
+                }
+                Reassembled::Discarded(len) => {
+                    debug!("discarding msg larger than the max msg size");
+                    self.recv_buffer.on_delivered(len as u32);
+                }
+                Reassembled::Pending => {}
+            },
+            verdict => debug!("{:?} i-data chunk with tsn {}", verdict, data.tsn()),
+        }
+        None
+    }
+
+    /// Hands msgs over to the ULP. With NR-SACK their DATA is reported
+    /// as non-renegable from now on
+    fn deliver(&mut self, msgs: Vec<Message>) {
+        if self.nr_sack {
+            for msg in &msgs {
+                self.recv_buffer.on_msg_delivered(&msg.tsns);
+            }
+        }
+        self.msg_queue.extend(msgs);
+    }
+
+    /// Skips the msgs the peer abandoned (RFC 8260 section 2.3.2)
+    fn handle_i_forward_tsn(&mut self, forward_tsn: IForwardTsn) {
+        if !self
+            .recv_buffer
+            .on_forward_tsn(forward_tsn.new_cumulative_tsn())
+        {
+            return;
+        }
+        let len = self
+            .interleaved_reassembly
+            .on_forward_tsn(forward_tsn.new_cumulative_tsn(), forward_tsn.streams());
+        self.recv_buffer.on_delivered(len as u32);
+        // unordered msgs aren't held back so only the ordered ones are skipped
+        for skipped in forward_tsn.streams().iter().filter(|s| !s.unordered) {
+            let msgs = self
+                .inbound_streams
+                .skip(skipped.stream_id, skipped.message_id);
+            self.deliver(msgs);
+        }
+    }
+
+    /// Skips the msgs the peer abandoned (RFC 3758 section 3.6)
+    fn handle_forward_tsn(&mut self, forward_tsn: ForwardTsn) {
+        if !self
+            .recv_buffer
+            .on_forward_tsn(forward_tsn.new_cumulative_tsn())
+        {
+            return;
+        }
+        // the fragments of the abandoned msgs will never be delivered
+        let len = self
+            .reassembly
+            .on_forward_tsn(forward_tsn.new_cumulative_tsn());
+        self.recv_buffer.on_delivered(len as u32);
+        for &(stream_id, stream_seq_no) in forward_tsn.streams() {
+            let msgs = self.inbound_streams.skip(stream_id, stream_seq_no.into());
+            self.deliver(msgs);
+        }
+    }
+
+    /// Sends a packet with a SACK chunk to the given addr, followed by
+    /// an ERROR chunk carrying the given causes, if any
+    async fn send_sack(
+        &mut self,
+        errors: Vec<Box<dyn Cause>>,
+        addr: SocketAddr,
+    ) -> Result<(), SCTPError> {
+        let mut packet = self.new_packet();
+        if self.nr_sack {
+            packet.add_chunk(Box::new(self.recv_buffer.nr_sack()));
+        } else {
+            packet.add_chunk(Box::new(self.recv_buffer.sack()));
+        }
+        // RFC 4960 appendix A: the ECNE goes with every SACK until the peer sends a CWR
+        if let Some(ecne) = self.ecn_receiver.ecne() {
+            packet.add_chunk(Box::new(ecne));
+        }
+        if !errors.is_empty() {
+            packet.add_chunk(Box::new(Error::new(errors)));
+        }
+        self.send_packet(&packet, addr).await
+    }
+
     /// Graceful termination of the association
     pub async fn terminate(&self) -> Result<(), SCTPError> {
         // TODO: abhi - send all pending msgs from local msg queue
@@ -439,15 +2520,38 @@ impl Association {
         let _ = self.msg_queue.drain(..);
         // 2. send ABORT chunk to peer
 
-        let mut packet = Packet::new(
-            self.local_addr.port(),
-            self.remote_addr.as_ref().unwrap().port(),
-        );
+        let mut packet = self.new_packet();
 
         // TODO abhi - pass a list of errors when creating the ABORT chunk
         packet.add_chunk(Box::new(Abort::new(None)));
 
-        self.stream.send(&Vec::<u8>::from(&packet)).await?;
+        self.send_packet(&packet, self.primary_addr()).await?;
         Ok(())
     }
 }
+
+/// Creates the authenticator of an association.
+/// ASCONFs are always authenticated as RFC 5061 requires.
+fn new_authenticator(config: &AssociationConfig) -> Authenticator {
+    let mut chunk_types = config.auth_chunks.clone();
+    chunk_types.extend(ASCONF_CHUNK_TYPES);
+    Authenticator::new(&chunk_types)
+}
+
+/// Returns true if there is at least one addr and all of them share a port,
+/// as an endpoint only has a single port (section 1.3.1)
+fn same_port(addrs: &[SocketAddr]) -> bool {
+    match addrs.first() {
+        Some(first) => addrs.iter().all(|addr| addr.port() == first.port()),
+        None => false,
+    }
+}
+
+/// Returns the size of the header of the IP packets sent to the given addr
+fn ip_header_size(addr: &SocketAddr) -> usize {
+    if addr.is_ipv6() {
+        IPV6_HEADER_SIZE
+    } else {
+        IPV4_HEADER_SIZE
+    }
+}
@@ -0,0 +1,299 @@
+use crate::chunk::{Auth, ParamType, Parameter};
+use crate::error::SCTPError;
+use rand::{thread_rng, RngCore};
+use ring::hmac;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Chunk type of the AUTH chunk, listed in the Supported Extensions param
+pub const AUTH_CHUNK_TYPE: u8 = 0x0f;
+// RFC 4895 section 3.1: the RANDOM value should be 32 bytes
+const RANDOM_LEN: usize = 32;
+// section 3.2: INIT, INIT ACK, SHUTDOWN COMPLETE and AUTH are never authenticated
+const UNAUTHENTICATED_CHUNK_TYPES: [u8; 4] = [1, 2, 14, AUTH_CHUNK_TYPE];
+
+/// HMAC algorithms of RFC 4895 section 6.1
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HmacAlgorithm {
+    Sha1,
+    Sha256,
+}
+
+// the algorithms we support in order of preference.
+// section 6.1: SHA-1 must always be listed
+const HMAC_ALGORITHMS: [HmacAlgorithm; 2] = [HmacAlgorithm::Sha256, HmacAlgorithm::Sha1];
+
+impl HmacAlgorithm {
+    pub fn id(self) -> u16 {
+        match self {
+            HmacAlgorithm::Sha1 => 1,
+            HmacAlgorithm::Sha256 => 3,
+        }
+    }
+
+    pub fn from_id(id: u16) -> Option<Self> {
+        match id {
+            1 => Some(HmacAlgorithm::Sha1),
+            3 => Some(HmacAlgorithm::Sha256),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the HMACs computed with this algorithm
+    pub fn hmac_len(self) -> usize {
+        match self {
+            HmacAlgorithm::Sha1 => 20,
+            HmacAlgorithm::Sha256 => 32,
+        }
+    }
+
+    fn algorithm(self) -> hmac::Algorithm {
+        match self {
+            HmacAlgorithm::Sha1 => hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY,
+            HmacAlgorithm::Sha256 => hmac::HMAC_SHA256,
+        }
+    }
+}
+
+/// The RANDOM, CHUNKS and HMAC-ALGO params an endpoint sent in its INIT/INIT ACK
+struct AuthParams {
+    random: Parameter,
+    chunks: Parameter,
+    hmacs: Parameter,
+}
+
+impl AuthParams {
+    /// Returns the key vector of the endpoint (section 6.1)
+    fn key_vector(&self) -> Vec<u8> {
+        key_vector(&[&self.random, &self.chunks, &self.hmacs])
+    }
+
+    fn chunk_types(&self) -> &[u8] {
+        &self.chunks.value
+    }
+
+    fn hmac_algorithms(&self) -> impl Iterator<Item = HmacAlgorithm> + '_ {
+        self.hmacs.value.chunks_exact(2).filter_map(|id| {
+            HmacAlgorithm::from_id(u16::from_be_bytes(<[u8; 2]>::try_from(id).unwrap()))
+        })
+    }
+}
+
+/// Returns the key vector made of the RANDOM, CHUNKS and HMAC-ALGO params of an endpoint.
+/// section 6.1: the params are used as they are sent, each one with its type
+/// and length, but without the padding that follows it
+pub fn key_vector(params: &[&Parameter]) -> Vec<u8> {
+    let mut v = vec![];
+    for param in params {
+        v.extend(u16::from(&param.param_type).to_be_bytes());
+        v.extend((4 + param.value.len() as u16).to_be_bytes());
+        v.extend(&param.value);
+    }
+    v
+}
+
+/// Authenticates the chunks of an association (RFC 4895).
+/// Holds the endpoint pair shared keys, the params exchanged in the handshake
+/// and the HMAC algorithm used for the chunks we send.
+pub struct Authenticator {
+    local: AuthParams,
+    peer: Option<AuthParams>,
+    // algorithm of the HMACs we send, picked from the peer's list
+    hmac: Option<HmacAlgorithm>,
+    // endpoint pair shared keys by key id
+    keys: HashMap<u16, Vec<u8>>,
+    active_key: u16,
+}
+
+impl Authenticator {
+    /// Creates an authenticator that asks the peer to authenticate the given chunk types
+    pub fn new(chunk_types: &[u8]) -> Self {
+        let mut random = vec![0u8; RANDOM_LEN];
+        thread_rng().fill_bytes(&mut random);
+
+        let mut chunks: Vec<u8> = chunk_types
+            .iter()
+            .copied()
+            .filter(|chunk_type| !UNAUTHENTICATED_CHUNK_TYPES.contains(chunk_type))
+            .collect();
+        chunks.sort_unstable();
+        chunks.dedup();
+
+        let hmacs = HMAC_ALGORITHMS
+            .iter()
+            .flat_map(|algorithm| algorithm.id().to_be_bytes())
+            .collect();
+
+        // section 6.1: the key with id 0 is the empty key unless the ULP sets one
+        let mut keys = HashMap::new();
+        keys.insert(0, vec![]);
+
+        Self {
+            local: AuthParams {
+                random: Parameter::new(ParamType::Random, random),
+                chunks: Parameter::new(ParamType::ChunkList, chunks),
+                hmacs: Parameter::new(ParamType::HmacAlgo, hmacs),
+            },
+            peer: None,
+            hmac: None,
+            keys,
+            active_key: 0,
+        }
+    }
+
+    /// Returns the RANDOM, CHUNKS and HMAC-ALGO params of our INIT/INIT ACK
+    pub fn params(&self) -> Vec<Parameter> {
+        vec![
+            self.local.random.clone(),
+            self.local.chunks.clone(),
+            self.local.hmacs.clone(),
+        ]
+    }
+
+    /// Negotiates AUTH from the params of the peer's INIT/INIT ACK.
+    /// AUTH is only used if the peer sent all three params
+    /// and supports one of our HMAC algorithms.
+    pub fn on_peer_params(&mut self, params: &[Parameter]) {
+        let find = |param_type: ParamType| {
+            params
+                .iter()
+                .find(|param| param.param_type == param_type)
+                .cloned()
+        };
+        let peer = match (
+            find(ParamType::Random),
+            find(ParamType::ChunkList),
+            find(ParamType::HmacAlgo),
+        ) {
+            (Some(random), Some(chunks), Some(hmacs)) => AuthParams {
+                random,
+                chunks,
+                hmacs,
+            },
+            _ => return,
+        };
+
+        self.hmac = peer
+            .hmac_algorithms()
+            .find(|algorithm| HMAC_ALGORITHMS.contains(algorithm));
+        if self.hmac.is_some() {
+            self.peer = Some(peer);
+        }
+    }
+
+    /// Returns true if both endpoints agreed to use AUTH
+    pub fn is_negotiated(&self) -> bool {
+        self.peer.is_some()
+    }
+
+    /// Returns true if we only accept chunks of this type when they are authenticated
+    pub fn requires_auth(&self, chunk_type: u8) -> bool {
+        self.is_negotiated() && self.local.chunk_types().contains(&chunk_type)
+    }
+
+    /// Returns true if the peer only accepts chunks of this type when they are authenticated
+    pub fn peer_requires_auth(&self, chunk_type: u8) -> bool {
+        self.peer
+            .as_ref()
+            .is_some_and(|peer| peer.chunk_types().contains(&chunk_type))
+    }
+
+    /// Adds or replaces an endpoint pair shared key
+    pub fn add_key(&mut self, key_id: u16, key: Vec<u8>) {
+        self.keys.insert(key_id, key);
+    }
+
+    /// Uses the given key for the chunks we send from now on
+    pub fn set_active_key(&mut self, key_id: u16) -> Result<(), SCTPError> {
+        if !self.keys.contains_key(&key_id) {
+            return Err(SCTPError::InvalidKeyId);
+        }
+        self.active_key = key_id;
+        Ok(())
+    }
+
+    /// Deletes an endpoint pair shared key. The active key can't be deleted.
+    pub fn delete_key(&mut self, key_id: u16) -> Result<(), SCTPError> {
+        if key_id == self.active_key || self.keys.remove(&key_id).is_none() {
+            return Err(SCTPError::InvalidKeyId);
+        }
+        Ok(())
+    }
+
+    /// Returns the association shared key derived from an endpoint pair shared key.
+    /// section 6.1: the key vectors follow the endpoint pair shared key,
+    /// the numerically smaller one first
+    fn association_key(&self, key_id: u16) -> Option<Vec<u8>> {
+        let peer = self.peer.as_ref()?;
+        let mut key = self.keys.get(&key_id)?.clone();
+        let local = self.local.key_vector();
+        let peer = peer.key_vector();
+        if compare_key_vectors(&local, &peer) == Ordering::Greater {
+            key.extend(peer);
+            key.extend(local);
+        } else {
+            key.extend(local);
+            key.extend(peer);
+        }
+        Some(key)
+    }
+
+    /// Creates the AUTH chunk authenticating the given chunks with the active key.
+    /// The HMAC covers the AUTH chunk itself with a zeroed HMAC followed by the chunks.
+    pub fn sign(&self, covered: &[u8]) -> Auth {
+        let algorithm = self.hmac.unwrap_or(HmacAlgorithm::Sha1);
+        let zeroed = Auth::new(
+            self.active_key,
+            algorithm.id(),
+            vec![0; algorithm.hmac_len()],
+        );
+        let key = self.association_key(self.active_key).unwrap_or_default();
+        let tag = hmac::sign(
+            &hmac::Key::new(algorithm.algorithm(), &key),
+            &authenticated_bytes(&zeroed, covered),
+        );
+        Auth::new(self.active_key, algorithm.id(), tag.as_ref().to_vec())
+    }
+
+    /// Verifies the HMAC of an AUTH chunk recvd along with the chunks it covers (section 6.3).
+    /// An HMAC whose length doesn't match its HMAC id fails before it is compared.
+    pub fn verify(&self, auth: &Auth, covered: &[u8]) -> bool {
+        let algorithm = match HmacAlgorithm::from_id(auth.hmac_id()) {
+            Some(algorithm) if auth.hmac().len() == algorithm.hmac_len() => algorithm,
+            _ => return false,
+        };
+        let key = match self.association_key(auth.key_id()) {
+            Some(key) => key,
+            None => return false,
+        };
+        let zeroed = Auth::new(auth.key_id(), auth.hmac_id(), vec![0; auth.hmac().len()]);
+        hmac::verify(
+            &hmac::Key::new(algorithm.algorithm(), &key),
+            &authenticated_bytes(&zeroed, covered),
+            auth.hmac(),
+        )
+        .is_ok()
+    }
+}
+
+/// Returns the bytes an HMAC is computed over
+fn authenticated_bytes(zeroed: &Auth, covered: &[u8]) -> Vec<u8> {
+    use crate::chunk::Chunk;
+
+    let mut v = zeroed.get_bytes();
+    v.extend(covered);
+    v
+}
+
+/// Compares two key vectors as big endian numbers.
+/// Vectors that are numerically equal are ordered by their length.
+fn compare_key_vectors(a: &[u8], b: &[u8]) -> Ordering {
+    let trim = |v: &[u8]| -> Vec<u8> { v.iter().copied().skip_while(|&byte| byte == 0).collect() };
+    let (trimmed_a, trimmed_b) = (trim(a), trim(b));
+    trimmed_a
+        .len()
+        .cmp(&trimmed_b.len())
+        .then_with(|| trimmed_a.cmp(&trimmed_b))
+        .then_with(|| a.len().cmp(&b.len()))
+}
@@ -2,19 +2,34 @@ use crate::cookie::Cookie;
 use rand::{thread_rng, Rng};
 use std::convert::TryFrom;
 use std::fmt::Debug;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum ChunkType {
     Data,
     Init,
     InitAck,
     Sack,
+    NrSack,
+    Heartbeat,
+    HeartbeatAck,
     Abort,
     Shutdown,
+    Error,
     CookieEcho,
     CookieAck,
+    Ecne,
+    Cwr,
     ShutdownComplete,
     ShutdownAck,
+    Auth,
+    IData,
+    AsconfAck,
+    ReConfig,
+    Pad,
+    ForwardTsn,
+    Asconf,
+    IForwardTsn,
     Invalid,
 }
 
@@ -25,18 +40,65 @@ impl From<u8> for ChunkType {
             1 => ChunkType::Init,
             2 => ChunkType::InitAck,
             3 => ChunkType::Sack,
+            4 => ChunkType::Heartbeat,
+            5 => ChunkType::HeartbeatAck,
             6 => ChunkType::Abort,
             7 => ChunkType::Shutdown,
             8 => ChunkType::ShutdownAck,
+            9 => ChunkType::Error,
             10 => ChunkType::CookieEcho,
             11 => ChunkType::CookieAck,
+            12 => ChunkType::Ecne,
+            13 => ChunkType::Cwr,
             14 => ChunkType::ShutdownComplete,
+            15 => ChunkType::Auth,
+            0x10 => ChunkType::NrSack,
+            0x40 => ChunkType::IData,
+            0x80 => ChunkType::AsconfAck,
+            0x82 => ChunkType::ReConfig,
+            0x84 => ChunkType::Pad,
+            0xc0 => ChunkType::ForwardTsn,
+            0xc1 => ChunkType::Asconf,
+            0xc2 => ChunkType::IForwardTsn,
             _ => ChunkType::Invalid,
         }
     }
 }
 
-pub trait Chunk {
+impl From<&ChunkType> for u8 {
+    fn from(chunk_type: &ChunkType) -> Self {
+        match chunk_type {
+            ChunkType::Data => 0,
+            ChunkType::Init => 1,
+            ChunkType::InitAck => 2,
+            ChunkType::Sack => 3,
+            ChunkType::Heartbeat => 4,
+            ChunkType::HeartbeatAck => 5,
+            ChunkType::Abort => 6,
+            ChunkType::Shutdown => 7,
+            ChunkType::ShutdownAck => 8,
+            ChunkType::Error => 9,
+            ChunkType::CookieEcho => 10,
+            ChunkType::CookieAck => 11,
+            ChunkType::Ecne => 12,
+            ChunkType::Cwr => 13,
+            ChunkType::ShutdownComplete => 14,
+            ChunkType::Auth => 15,
+            ChunkType::NrSack => 0x10,
+            ChunkType::IData => 0x40,
+            ChunkType::AsconfAck => 0x80,
+            ChunkType::ReConfig => 0x82,
+            ChunkType::Pad => 0x84,
+            ChunkType::ForwardTsn => 0xc0,
+            ChunkType::Asconf => 0xc1,
+            ChunkType::IForwardTsn => 0xc2,
+            // reserved by the IETF so it never shows up on the wire
+            ChunkType::Invalid => 0xff,
+        }
+    }
+}
+
+pub trait Chunk: Debug {
     fn get_bytes(&self) -> Vec<u8>;
     fn chunk_type(&self) -> ChunkType;
 }
@@ -74,6 +136,20 @@ impl ChunkHeader {
             length,
         }
     }
+
+    /// Parses the chunk header at the start of `buf`
+    pub fn parse(buf: &[u8]) -> Self {
+        Self::new(
+            buf[0],
+            buf[1],
+            u16::from_be_bytes(<[u8; 2]>::try_from(&buf[2..=3]).unwrap()),
+        )
+    }
+
+    /// Returns the length of the chunk excluding any padding
+    pub fn length(&self) -> u16 {
+        self.length
+    }
 }
 
 impl From<&ChunkHeader> for [u8; 4] {
@@ -101,11 +177,37 @@ impl From<&ChunkHeader> for [u8; 4] {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum ParamType {
+    HeartbeatInfo,
+    Ipv4Addr,
+    Ipv6Addr,
     StateCookie,
     HostNameAddr,
+    OutgoingSsnReset,
+    IncomingSsnReset,
+    SsnTsnReset,
+    ReconfigResponse,
+    AddOutgoingStreams,
+    AddIncomingStreams,
+    EcnCapable,
+    ZeroChecksumAcceptable,
+    Random,
+    ChunkList,
+    HmacAlgo,
+    SupportedExtensions,
+    ForwardTsnSupported,
+    AddIpAddr,
+    DeleteIpAddr,
+    ErrorCauseIndication,
+    SetPrimaryAddr,
+    SuccessIndication,
+    AdaptationLayerIndication,
     Invalid, // TODO abhi - add other params as and when required
 }
 
+/// Error detection method identifier of SCTP over DTLS, which can stand in for
+/// the CRC32c of packets (RFC 9653 section 7)
+pub const EDMID_DTLS: u32 = 1;
+
 #[derive(Clone, Debug)]
 pub struct Parameter {
     pub param_type: ParamType,
@@ -121,13 +223,99 @@ impl Parameter {
             value,
         }
     }
+
+    /// Creates an IPv4/IPv6 Address param
+    pub fn from_ip(ip: IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(ip) => Self::new(ParamType::Ipv4Addr, ip.octets().to_vec()),
+            IpAddr::V6(ip) => Self::new(ParamType::Ipv6Addr, ip.octets().to_vec()),
+        }
+    }
+
+    /// Returns the address carried by an IPv4/IPv6 Address param
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self.param_type {
+            ParamType::Ipv4Addr => <[u8; 4]>::try_from(&self.value[..])
+                .ok()
+                .map(|octets| IpAddr::V4(Ipv4Addr::from(octets))),
+            ParamType::Ipv6Addr => <[u8; 16]>::try_from(&self.value[..])
+                .ok()
+                .map(|octets| IpAddr::V6(Ipv6Addr::from(octets))),
+            _ => None,
+        }
+    }
+
+    /// Creates a Supported Extensions param listing the given chunk types (RFC 5061 section 4.2.7)
+    pub fn supported_extensions(chunk_types: &[u8]) -> Self {
+        Self::new(ParamType::SupportedExtensions, chunk_types.to_vec())
+    }
+
+    /// Creates a Zero Checksum Acceptable param announcing the alternate
+    /// error detection method used in place of the CRC32c (RFC 9653 section 4)
+    pub fn zero_checksum_acceptable(edmid: u32) -> Self {
+        Self::new(
+            ParamType::ZeroChecksumAcceptable,
+            edmid.to_be_bytes().to_vec(),
+        )
+    }
+
+    /// Returns the error detection method identifier of a Zero Checksum Acceptable param
+    pub fn edmid(&self) -> Option<u32> {
+        match self.param_type {
+            ParamType::ZeroChecksumAcceptable => <[u8; 4]>::try_from(&self.value[..])
+                .ok()
+                .map(u32::from_be_bytes),
+            _ => None,
+        }
+    }
+
+    /// Creates an Adaptation Layer Indication param carrying the given
+    /// adaptation code point (RFC 5061 section 4.2.6)
+    pub fn adaptation_layer_indication(code_point: u32) -> Self {
+        Self::new(
+            ParamType::AdaptationLayerIndication,
+            code_point.to_be_bytes().to_vec(),
+        )
+    }
+
+    /// Returns the adaptation code point of an Adaptation Layer Indication param
+    pub fn adaptation_code_point(&self) -> Option<u32> {
+        match self.param_type {
+            ParamType::AdaptationLayerIndication => <[u8; 4]>::try_from(&self.value[..])
+                .ok()
+                .map(u32::from_be_bytes),
+            _ => None,
+        }
+    }
 }
 
 impl From<&ParamType> for u16 {
     fn from(param_type: &ParamType) -> Self {
         match param_type {
+            ParamType::HeartbeatInfo => 1,
+            ParamType::Ipv4Addr => 5,
+            ParamType::Ipv6Addr => 6,
             ParamType::StateCookie => 7,
             ParamType::HostNameAddr => 11,
+            ParamType::OutgoingSsnReset => 13,
+            ParamType::IncomingSsnReset => 14,
+            ParamType::SsnTsnReset => 15,
+            ParamType::ReconfigResponse => 16,
+            ParamType::AddOutgoingStreams => 17,
+            ParamType::AddIncomingStreams => 18,
+            ParamType::EcnCapable => 0x8000,
+            ParamType::ZeroChecksumAcceptable => 0x8001,
+            ParamType::Random => 0x8002,
+            ParamType::ChunkList => 0x8003,
+            ParamType::HmacAlgo => 0x8004,
+            ParamType::SupportedExtensions => 0x8008,
+            ParamType::ForwardTsnSupported => 0xc000,
+            ParamType::AddIpAddr => 0xc001,
+            ParamType::DeleteIpAddr => 0xc002,
+            ParamType::ErrorCauseIndication => 0xc003,
+            ParamType::SetPrimaryAddr => 0xc004,
+            ParamType::SuccessIndication => 0xc005,
+            ParamType::AdaptationLayerIndication => 0xc006,
             ParamType::Invalid => 0,
         }
     }
@@ -136,8 +324,30 @@ impl From<&ParamType> for u16 {
 impl From<u16> for ParamType {
     fn from(param_type: u16) -> Self {
         match param_type {
+            1 => ParamType::HeartbeatInfo,
+            5 => ParamType::Ipv4Addr,
+            6 => ParamType::Ipv6Addr,
             7 => ParamType::StateCookie,
             11 => ParamType::HostNameAddr,
+            13 => ParamType::OutgoingSsnReset,
+            14 => ParamType::IncomingSsnReset,
+            15 => ParamType::SsnTsnReset,
+            16 => ParamType::ReconfigResponse,
+            17 => ParamType::AddOutgoingStreams,
+            18 => ParamType::AddIncomingStreams,
+            0x8000 => ParamType::EcnCapable,
+            0x8001 => ParamType::ZeroChecksumAcceptable,
+            0x8002 => ParamType::Random,
+            0x8003 => ParamType::ChunkList,
+            0x8004 => ParamType::HmacAlgo,
+            0x8008 => ParamType::SupportedExtensions,
+            0xc000 => ParamType::ForwardTsnSupported,
+            0xc001 => ParamType::AddIpAddr,
+            0xc002 => ParamType::DeleteIpAddr,
+            0xc003 => ParamType::ErrorCauseIndication,
+            0xc004 => ParamType::SetPrimaryAddr,
+            0xc005 => ParamType::SuccessIndication,
+            0xc006 => ParamType::AdaptationLayerIndication,
             _ => ParamType::Invalid,
         }
     }
@@ -149,10 +359,31 @@ impl From<&Parameter> for Vec<u8> {
         v.extend(u16::from(&p.param_type).to_be_bytes());
         v.extend(p.len.to_be_bytes());
         v.extend(&p.value);
+        v.extend(std::iter::repeat_n(0, padding(p.value.len())));
         v
     }
 }
 
+/// Returns the number of bytes needed to pad `len` to a multiple of 4
+pub fn padding(len: usize) -> usize {
+    let diff = len % 4;
+    if diff == 0 {
+        0
+    } else {
+        4 - diff
+    }
+}
+
+/// Returns the number of bytes the given params take up on the wire
+fn params_len(params: &Option<Vec<Parameter>>) -> u16 {
+    params.as_ref().map_or(0, |params| {
+        params
+            .iter()
+            .map(|p| 4 + p.value.len() + padding(p.value.len()))
+            .sum::<usize>() as u16
+    })
+}
+
 /*
         0                   1                   2                   3
         0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
@@ -178,9 +409,9 @@ pub struct Init {
     header: ChunkHeader,
     pub init_tag: u32,
     pub a_rwnd: u32,
-    num_ob_streams: u16,
-    num_ib_streams: u16,
-    init_tsn: u32,
+    pub num_ob_streams: u16,
+    pub num_ib_streams: u16,
+    pub init_tsn: u32,
     pub optional_params: Option<Vec<Parameter>>,
 }
 
@@ -193,11 +424,7 @@ impl Init {
         optional_params: Option<Vec<Parameter>>,
     ) -> Self {
         Self {
-            header: ChunkHeader::new(
-                1,
-                0,
-                20 + optional_params.as_ref().map_or(0, |v| *(&v.len()) as u16),
-            ),
+            header: ChunkHeader::new(1, 0, 20 + params_len(&optional_params)),
             init_tag,
             a_rwnd,
             num_ob_streams,
@@ -289,31 +516,30 @@ impl Chunk for Init {
 #[derive(Clone, Debug)]
 pub struct InitAck {
     header: ChunkHeader,
-    init_tag: u32,
-    a_rwnd: u32,
-    num_ob_streams: u16,
-    num_ib_streams: u16,
-    init_tsn: u32,
+    pub init_tag: u32,
+    pub a_rwnd: u32,
+    pub num_ob_streams: u16,
+    pub num_ib_streams: u16,
+    pub init_tsn: u32,
     pub optional_params: Option<Vec<Parameter>>,
 }
 
 impl InitAck {
-    pub fn new(init: Init) -> Self {
+    pub fn new(
+        init_tag: u32,
+        a_rwnd: u32,
+        num_ob_streams: u16,
+        num_ib_streams: u16,
+        optional_params: Option<Vec<Parameter>>,
+    ) -> Self {
         Self {
-            header: ChunkHeader::new(
-                2,
-                0,
-                20 + init
-                    .optional_params
-                    .as_ref()
-                    .map_or(0, |v| *(&v.len()) as u16),
-            ),
-            init_tag: init.init_tag,
-            a_rwnd: init.a_rwnd,
-            num_ob_streams: init.num_ob_streams,
-            num_ib_streams: init.num_ib_streams,
+            header: ChunkHeader::new(2, 0, 20 + params_len(&optional_params)),
+            init_tag,
+            a_rwnd,
+            num_ob_streams,
+            num_ib_streams,
             init_tsn: thread_rng().gen_range(0..=4294967295),
-            optional_params: init.optional_params,
+            optional_params,
         }
     }
 
@@ -323,6 +549,7 @@ impl InitAck {
         } else {
             self.optional_params = Some(vec![param]);
         }
+        self.header.length = 20 + params_len(&self.optional_params);
     }
 }
 
@@ -370,18 +597,22 @@ fn parse_optional_params(buf: &[u8], start_offset: usize) -> Option<Vec<Paramete
     //      construct a param and push it into the optional_params vec
     //      repeat
     let mut offset = start_offset;
-    if offset > buf.len() - 1 {
+    if offset >= buf.len() {
         None
     } else {
         let mut v = vec![];
 
-        while offset < buf.len() {
+        // a param that doesn't fit in the rest of the buffer ends the list
+        while offset + 4 <= buf.len() {
             let param_type =
                 u16::from_be_bytes(<[u8; 2]>::try_from(&buf[offset..=(offset + 1)]).unwrap())
                     .into();
             offset += 2;
             let len = u16::from_be_bytes(<[u8; 2]>::try_from(&buf[offset..=(offset + 1)]).unwrap());
             offset += 2;
+            if offset + len as usize > buf.len() {
+                break;
+            }
             let value = &buf[offset..offset + len as usize];
 
             v.push(Parameter {
@@ -390,7 +621,7 @@ fn parse_optional_params(buf: &[u8], start_offset: usize) -> Option<Vec<Paramete
                 value: value.to_vec(),
             });
 
-            offset += len as usize;
+            offset += len as usize + padding(len as usize);
         }
 
         Some(v)
@@ -538,6 +769,10 @@ impl Chunk for CookieAck {
        +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 */
 
+const DATA_FLAG_END: u8 = 0x01;
+const DATA_FLAG_BEGINNING: u8 = 0x02;
+const DATA_FLAG_UNORDERED: u8 = 0x04;
+
 #[derive(Clone, Debug)]
 pub struct Data {
     header: ChunkHeader,
@@ -561,17 +796,15 @@ impl Data {
         mut data: Vec<u8>,
     ) -> Self {
         let flag = match (start, end) {
-            (true, false) => 6,
-            (false, true) => 1,
-            (true, true) => 7,
+            (true, false) => DATA_FLAG_BEGINNING,
+            (false, true) => DATA_FLAG_END,
+            (true, true) => DATA_FLAG_BEGINNING | DATA_FLAG_END,
             _ => 0,
         };
 
         // pad with 0s if data len not multiple of 4
         let unpadded_data_len = data.len();
-        let diff = unpadded_data_len % 4;
-        let adjust = if diff == 0 { 0 } else { 4 - diff };
-        data.extend(std::iter::repeat(0).take(adjust));
+        data.extend(std::iter::repeat(0).take(padding(unpadded_data_len)));
 
         Self {
             header: ChunkHeader::new(0, flag, 16 + unpadded_data_len as u16),
@@ -582,27 +815,88 @@ impl Data {
             data,
         }
     }
+
+    /// Sets the U flag so the msg is delivered without regard to its stream seq no
+    pub fn unordered(mut self, unordered: bool) -> Self {
+        if unordered {
+            self.header.flags |= DATA_FLAG_UNORDERED;
+        } else {
+            self.header.flags &= !DATA_FLAG_UNORDERED;
+        }
+        self
+    }
+
+    /// Sets the TSN of a chunk that was created before it was scheduled for transmission
+    pub fn with_tsn(mut self, tsn: u32) -> Self {
+        self.tsn = tsn;
+        self
+    }
+
+    pub fn tsn(&self) -> u32 {
+        self.tsn
+    }
+
+    pub fn stream_id(&self) -> u16 {
+        self.stream_id
+    }
+
+    pub fn stream_seq_no(&self) -> u16 {
+        self.stream_seq_no
+    }
+
+    pub fn payload_proto_id(&self) -> u32 {
+        self.payload_proto_id
+    }
+
+    /// Returns true if this is the first fragment of a msg
+    pub fn is_beginning(&self) -> bool {
+        self.header.flags & DATA_FLAG_BEGINNING != 0
+    }
+
+    /// Returns true if this is the last fragment of a msg
+    pub fn is_end(&self) -> bool {
+        self.header.flags & DATA_FLAG_END != 0
+    }
+
+    /// Returns true if the U flag is set
+    pub fn is_unordered(&self) -> bool {
+        self.header.flags & DATA_FLAG_UNORDERED != 0
+    }
+
+    /// Returns the length of the user data excluding any padding
+    pub fn payload_len(&self) -> usize {
+        self.header.length as usize - 16
+    }
+
+    /// Returns the user data excluding any padding
+    pub fn payload(&self) -> &[u8] {
+        &self.data[..self.payload_len()]
+    }
 }
 
 impl From<&[u8]> for Data {
     fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = header.length as usize;
         Self {
-            header: ChunkHeader::new(
-                buf[0],
-                buf[1],
-                u16::from_be_bytes(<[u8; 2]>::try_from(&buf[2..=3]).unwrap()),
-            ),
+            header,
             tsn: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..=7]).unwrap()),
-            stream_id: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[7..=8]).unwrap()),
+            stream_id: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[8..=9]).unwrap()),
             // stream_seq_no will be the same for
             // fragments of the same msg
-            stream_seq_no: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[9..=10]).unwrap()),
-            payload_proto_id: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[11..=14]).unwrap()),
-            data: buf[15..].to_vec(),
+            stream_seq_no: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[10..=11]).unwrap()),
+            payload_proto_id: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[12..=15]).unwrap()),
+            data: buf[16..len].to_vec(),
         }
     }
 }
 
+impl From<Vec<u8>> for Data {
+    fn from(buf: Vec<u8>) -> Self {
+        Data::from(&buf[..])
+    }
+}
+
 impl Chunk for Data {
     fn get_bytes(&self) -> Vec<u8> {
         let mut v = vec![];
@@ -620,6 +914,180 @@ impl Chunk for Data {
     }
 }
 
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |   Type = 64   |  Res  |I|U|B|E|       Length = Variable       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                              TSN                              |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |        Stream Identifier      |           Reserved            |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                      Message Identifier                       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |    Payload Protocol Identifier / Fragment Sequence Number     |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /                           User Data                           /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+/// Chunk type of the I-DATA chunk, listed in the Supported Extensions param
+pub const I_DATA_CHUNK_TYPE: u8 = 0x40;
+/// Chunk type of the I-FORWARD-TSN chunk, listed in the Supported Extensions param
+pub const I_FORWARD_TSN_CHUNK_TYPE: u8 = 0xc2;
+
+/// Carries user data like DATA but identifies the fragments of a msg by a message id
+/// and a fragment seq no instead of consecutive TSNs, so the fragments of different msgs
+/// can be interleaved (RFC 8260 section 2.1)
+#[derive(Clone, Debug)]
+pub struct IData {
+    header: ChunkHeader,
+    tsn: u32,
+    stream_id: u16,
+    // the same for all the fragments of a msg
+    message_id: u32,
+    // the ppid in the first fragment and the fragment seq no in the others
+    ppid_or_fsn: u32,
+    pub data: Vec<u8>,
+}
+
+impl IData {
+    /// Creates a fragment of a msg. The first fragment carries the ppid
+    /// and has fragment seq no 0; the others carry `fsn`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        tsn: u32,
+        stream_id: u16,
+        message_id: u32,
+        fsn: u32,
+        payload_proto_id: u32,
+        start: bool,
+        end: bool,
+        mut data: Vec<u8>,
+    ) -> Self {
+        let mut flag = 0;
+        if start {
+            flag |= DATA_FLAG_BEGINNING;
+        }
+        if end {
+            flag |= DATA_FLAG_END;
+        }
+
+        let unpadded_data_len = data.len();
+        data.resize(unpadded_data_len + padding(unpadded_data_len), 0);
+
+        Self {
+            header: ChunkHeader::new(0x40, flag, 20 + unpadded_data_len as u16),
+            tsn,
+            stream_id,
+            message_id,
+            ppid_or_fsn: if start { payload_proto_id } else { fsn },
+            data,
+        }
+    }
+
+    /// Sets the U flag so the msg is delivered without regard to its message id
+    pub fn unordered(mut self, unordered: bool) -> Self {
+        if unordered {
+            self.header.flags |= DATA_FLAG_UNORDERED;
+        } else {
+            self.header.flags &= !DATA_FLAG_UNORDERED;
+        }
+        self
+    }
+
+    pub fn tsn(&self) -> u32 {
+        self.tsn
+    }
+
+    pub fn stream_id(&self) -> u16 {
+        self.stream_id
+    }
+
+    pub fn message_id(&self) -> u32 {
+        self.message_id
+    }
+
+    /// Returns the position of this fragment in its msg, 0 for the first one
+    pub fn fragment_seq_no(&self) -> u32 {
+        if self.is_beginning() {
+            0
+        } else {
+            self.ppid_or_fsn
+        }
+    }
+
+    /// Returns the ppid, which only the first fragment of a msg carries
+    pub fn payload_proto_id(&self) -> Option<u32> {
+        if self.is_beginning() {
+            Some(self.ppid_or_fsn)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if this is the first fragment of a msg
+    pub fn is_beginning(&self) -> bool {
+        self.header.flags & DATA_FLAG_BEGINNING != 0
+    }
+
+    /// Returns true if this is the last fragment of a msg
+    pub fn is_end(&self) -> bool {
+        self.header.flags & DATA_FLAG_END != 0
+    }
+
+    /// Returns true if the U flag is set
+    pub fn is_unordered(&self) -> bool {
+        self.header.flags & DATA_FLAG_UNORDERED != 0
+    }
+
+    /// Returns the length of the user data excluding any padding
+    pub fn payload_len(&self) -> usize {
+        (self.header.length as usize).saturating_sub(20)
+    }
+
+    /// Returns the user data excluding any padding
+    pub fn payload(&self) -> &[u8] {
+        &self.data[..self.payload_len().min(self.data.len())]
+    }
+}
+
+impl From<&[u8]> for IData {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = (header.length as usize).clamp(20, buf.len());
+        Self {
+            header,
+            tsn: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+            stream_id: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[8..10]).unwrap()),
+            message_id: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[12..16]).unwrap()),
+            ppid_or_fsn: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[16..20]).unwrap()),
+            data: buf[20..len].to_vec(),
+        }
+    }
+}
+
+impl Chunk for IData {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.tsn.to_be_bytes());
+        v.extend(self.stream_id.to_be_bytes());
+        v.extend([0u8; 2]);
+        v.extend(self.message_id.to_be_bytes());
+        v.extend(self.ppid_or_fsn.to_be_bytes());
+        v.extend(&self.data);
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
 /*
         0                   1                   2                   3
         0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
@@ -654,7 +1122,7 @@ impl Chunk for Data {
 pub struct Sack {
     header: ChunkHeader,
     pub cumulative_tsn_ack: u32,
-    a_rwnd: u32,
+    pub a_rwnd: u32,
     num_gap_ack_blocks: u16,
     num_dup_tsns: u16,
     gap_ack_blk_starts_ends: Option<Vec<(u16, u16)>>,
@@ -687,6 +1155,16 @@ impl Sack {
             dup_tsns,
         }
     }
+
+    /// Returns the gap ack blocks as (start, end) offsets from the cumulative TSN ack
+    pub fn gap_ack_blocks(&self) -> &[(u16, u16)] {
+        self.gap_ack_blk_starts_ends.as_deref().unwrap_or(&[])
+    }
+
+    /// Returns the TSNs that were received more than once
+    pub fn dup_tsns(&self) -> &[u32] {
+        self.dup_tsns.as_deref().unwrap_or(&[])
+    }
 }
 
 impl Chunk for Sack {
@@ -720,6 +1198,12 @@ impl Chunk for Sack {
 
 impl From<Vec<u8>> for Sack {
     fn from(buf: Vec<u8>) -> Self {
+        Sack::from(&buf[..])
+    }
+}
+
+impl From<&[u8]> for Sack {
+    fn from(buf: &[u8]) -> Self {
         let header = ChunkHeader::new(
             buf[0],
             buf[1],
@@ -730,9 +1214,13 @@ impl From<Vec<u8>> for Sack {
         let num_gap_ack_blocks = u16::from_be_bytes(<[u8; 2]>::try_from(&buf[12..=13]).unwrap());
         let num_dup_tsns = u16::from_be_bytes(<[u8; 2]>::try_from(&buf[14..=15]).unwrap());
         let mut offset = 16usize;
+        // blocks and dup TSNs that don't fit in the chunk are ignored
         let gap_ack_blk_starts_ends = if num_gap_ack_blocks > 0 {
             let mut gaps = vec![];
             for _ in 0..num_gap_ack_blocks {
+                if offset + 4 > buf.len() {
+                    break;
+                }
                 gaps.push((
                     u16::from_be_bytes(<[u8; 2]>::try_from(&buf[offset..=offset + 1]).unwrap()),
                     u16::from_be_bytes(<[u8; 2]>::try_from(&buf[offset + 2..=offset + 3]).unwrap()),
@@ -747,8 +1235,11 @@ impl From<Vec<u8>> for Sack {
         let dup_tsns = if num_dup_tsns > 0 {
             let mut tsns = vec![];
             for _ in 0..num_dup_tsns {
+                if offset + 4 > buf.len() {
+                    break;
+                }
                 tsns.push(u32::from_be_bytes(
-                    <[u8; 4]>::try_from(&buf[offset..=offset + 4]).unwrap(),
+                    <[u8; 4]>::try_from(&buf[offset..offset + 4]).unwrap(),
                 ));
                 offset += 4;
             }
@@ -768,55 +1259,398 @@ impl From<Vec<u8>> for Sack {
         }
     }
 }
+
 /*
         0                   1                   2                   3
         0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
        +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
-       |   Type = 9    | Chunk  Flags  |           Length              |
+       |   Type = 16   |Chunk Flags  |A|      Chunk Length             |
        +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
-       \                                                               \
-       /                    one or more Error Causes                   /
-       \                                                               \
+       |                      Cumulative TSN Ack                       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |          Advertised Receiver Window Credit (a_rwnd)           |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Number of R Gap Ack Blocks = N| Number of NR Gap Ack Blocks = M|
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Number of Duplicate TSNs = X  |           Reserved            |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |  R Gap Ack Block #1 Start     |   R Gap Ack Block #1 End       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       /                              ...                              /
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |  NR Gap Ack Block #1 Start    |   NR Gap Ack Block #1 End      |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       /                              ...                              /
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                       Duplicate TSN 1                         |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       /                              ...                              /
        +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
 */
 
-pub trait Cause: Debug {
-    fn get_bytes(&self) -> Vec<u8>;
-}
+/// Chunk type of the NR-SACK chunk, listed in the Supported Extensions param
+pub const NR_SACK_CHUNK_TYPE: u8 = 0x10;
 
+/// A SACK that also reports the out of order DATA the receiver will never renege on,
+/// which the sender can free right away (draft-natarajan-tsvwg-sctp-nrsack).
+/// A TSN is reported in either the R or the NR gap ack blocks, not both.
 #[derive(Clone, Debug)]
-pub struct CauseHeader {
-    code: u16,
-    len: u16,
+pub struct NrSack {
+    header: ChunkHeader,
+    cumulative_tsn_ack: u32,
+    a_rwnd: u32,
+    gap_ack_blocks: Vec<(u16, u16)>,
+    nr_gap_ack_blocks: Vec<(u16, u16)>,
+    dup_tsns: Vec<u32>,
 }
 
-#[derive(Clone, Debug)]
-pub struct InvalidStreamId {
-    header: CauseHeader,
-    id: u16,
-    reserved: u16,
-}
+impl NrSack {
+    pub fn new(
+        cumulative_tsn_ack: u32,
+        a_rwnd: u32,
+        gap_ack_blocks: Vec<(u16, u16)>,
+        nr_gap_ack_blocks: Vec<(u16, u16)>,
+        dup_tsns: Vec<u32>,
+    ) -> Self {
+        let len = 20 + 4 * (gap_ack_blocks.len() + nr_gap_ack_blocks.len() + dup_tsns.len());
+        Self {
+            header: ChunkHeader::new(NR_SACK_CHUNK_TYPE, 0, len as u16),
+            cumulative_tsn_ack,
+            a_rwnd,
+            gap_ack_blocks,
+            nr_gap_ack_blocks,
+            dup_tsns,
+        }
+    }
 
-impl Cause for InvalidStreamId {
-    fn get_bytes(&self) -> Vec<u8> {
-        todo!()
+    pub fn cumulative_tsn_ack(&self) -> u32 {
+        self.cumulative_tsn_ack
     }
-}
 
-#[derive(Clone, Debug)]
-pub struct MissingMandatoryParam {
-    header: CauseHeader,
-    num: u32,
-    params: Vec<ParamType>,
-}
+    pub fn a_rwnd(&self) -> u32 {
+        self.a_rwnd
+    }
 
-impl Cause for MissingMandatoryParam {
-    fn get_bytes(&self) -> Vec<u8> {
-        todo!()
+    /// Returns the renegable gap ack blocks as (start, end) offsets from the cumulative TSN ack
+    pub fn gap_ack_blocks(&self) -> &[(u16, u16)] {
+        &self.gap_ack_blocks
     }
-}
 
-#[derive(Clone, Debug)]
+    /// Returns the non-renegable gap ack blocks as (start, end) offsets from the cumulative TSN ack
+    pub fn nr_gap_ack_blocks(&self) -> &[(u16, u16)] {
+        &self.nr_gap_ack_blocks
+    }
+
+    /// Returns the TSNs that were received more than once
+    pub fn dup_tsns(&self) -> &[u32] {
+        &self.dup_tsns
+    }
+}
+
+impl From<&[u8]> for NrSack {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = (header.length() as usize).clamp(20, buf.len());
+        let count = |offset: usize| {
+            u16::from_be_bytes(<[u8; 2]>::try_from(&buf[offset..offset + 2]).unwrap()) as usize
+        };
+        let (num_gap_ack_blocks, num_nr_gap_ack_blocks, num_dup_tsns) =
+            (count(12), count(14), count(16));
+
+        // counts that run past the end of the chunk are cut short
+        let mut words = buf[20..len].chunks_exact(4);
+        let mut blocks = |num: usize| -> Vec<(u16, u16)> {
+            words
+                .by_ref()
+                .take(num)
+                .map(|b| {
+                    (
+                        u16::from_be_bytes(<[u8; 2]>::try_from(&b[..2]).unwrap()),
+                        u16::from_be_bytes(<[u8; 2]>::try_from(&b[2..]).unwrap()),
+                    )
+                })
+                .collect()
+        };
+        let gap_ack_blocks = blocks(num_gap_ack_blocks);
+        let nr_gap_ack_blocks = blocks(num_nr_gap_ack_blocks);
+        let dup_tsns = words
+            .take(num_dup_tsns)
+            .map(|t| u32::from_be_bytes(<[u8; 4]>::try_from(t).unwrap()))
+            .collect();
+
+        Self {
+            header,
+            cumulative_tsn_ack: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+            a_rwnd: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[8..12]).unwrap()),
+            gap_ack_blocks,
+            nr_gap_ack_blocks,
+            dup_tsns,
+        }
+    }
+}
+
+impl Chunk for NrSack {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.cumulative_tsn_ack.to_be_bytes());
+        v.extend(self.a_rwnd.to_be_bytes());
+        v.extend((self.gap_ack_blocks.len() as u16).to_be_bytes());
+        v.extend((self.nr_gap_ack_blocks.len() as u16).to_be_bytes());
+        v.extend((self.dup_tsns.len() as u16).to_be_bytes());
+        v.extend([0u8; 2]);
+        for (start, end) in self.gap_ack_blocks.iter().chain(&self.nr_gap_ack_blocks) {
+            v.extend(start.to_be_bytes());
+            v.extend(end.to_be_bytes());
+        }
+        for dup_tsn in &self.dup_tsns {
+            v.extend(dup_tsn.to_be_bytes());
+        }
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |   Type = 4    | Chunk  Flags  |      Heartbeat Length         |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /            Heartbeat Information TLV (Variable-Length)        /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+#[derive(Clone, Debug)]
+pub struct Heartbeat {
+    header: ChunkHeader,
+    info: Parameter,
+}
+
+impl Heartbeat {
+    /// Creates a HEARTBEAT carrying the given sender-specific info
+    pub fn new(info: Vec<u8>) -> Self {
+        let info = Parameter::new(ParamType::HeartbeatInfo, info);
+        Self {
+            header: ChunkHeader::new(4, 0, 4 + params_len(&Some(vec![info.clone()]))),
+            info,
+        }
+    }
+
+    /// Returns the heartbeat info to echo back in the HEARTBEAT ACK
+    pub fn info(&self) -> &[u8] {
+        &self.info.value
+    }
+}
+
+impl From<&[u8]> for Heartbeat {
+    fn from(buf: &[u8]) -> Self {
+        Self {
+            header: ChunkHeader::parse(buf),
+            info: parse_heartbeat_info(buf),
+        }
+    }
+}
+
+impl Chunk for Heartbeat {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(Vec::<u8>::from(&self.info));
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |   Type = 5    | Chunk  Flags  |    Heartbeat Ack Length       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /            Heartbeat Information TLV (Variable-Length)        /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+#[derive(Clone, Debug)]
+pub struct HeartbeatAck {
+    header: ChunkHeader,
+    info: Parameter,
+}
+
+impl HeartbeatAck {
+    /// Creates a HEARTBEAT ACK echoing the info of a recvd HEARTBEAT
+    pub fn new(info: Vec<u8>) -> Self {
+        let info = Parameter::new(ParamType::HeartbeatInfo, info);
+        Self {
+            header: ChunkHeader::new(5, 0, 4 + params_len(&Some(vec![info.clone()]))),
+            info,
+        }
+    }
+
+    /// Returns the heartbeat info echoed by the peer
+    pub fn info(&self) -> &[u8] {
+        &self.info.value
+    }
+}
+
+impl From<&[u8]> for HeartbeatAck {
+    fn from(buf: &[u8]) -> Self {
+        Self {
+            header: ChunkHeader::parse(buf),
+            info: parse_heartbeat_info(buf),
+        }
+    }
+}
+
+impl Chunk for HeartbeatAck {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(Vec::<u8>::from(&self.info));
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/// Returns the heartbeat info param of a HEARTBEAT/HEARTBEAT ACK.
+/// An empty param is returned if the chunk doesn't carry one.
+fn parse_heartbeat_info(buf: &[u8]) -> Parameter {
+    let len = (ChunkHeader::parse(buf).length() as usize).min(buf.len());
+    parse_optional_params(&buf[..len], 4)
+        .and_then(|params| {
+            params
+                .into_iter()
+                .find(|param| param.param_type == ParamType::HeartbeatInfo)
+        })
+        .unwrap_or_else(|| Parameter::new(ParamType::HeartbeatInfo, vec![]))
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |   Type = 9    | Chunk  Flags  |           Length              |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /                    one or more Error Causes                   /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+pub trait Cause: Debug {
+    fn get_bytes(&self) -> Vec<u8>;
+}
+
+#[derive(Clone, Debug)]
+pub struct CauseHeader {
+    code: u16,
+    len: u16,
+}
+
+impl CauseHeader {
+    /// Parses the cause header at the start of `buf`
+    pub fn parse(buf: &[u8]) -> Self {
+        Self {
+            code: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[0..=1]).unwrap()),
+            len: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[2..=3]).unwrap()),
+        }
+    }
+}
+
+impl From<&CauseHeader> for [u8; 4] {
+    fn from(ch: &CauseHeader) -> Self {
+        let mut v = [0u8; 4];
+        v[0..2].copy_from_slice(&ch.code.to_be_bytes());
+        v[2..4].copy_from_slice(&ch.len.to_be_bytes());
+        v
+    }
+}
+
+const INVALID_STREAM_ID_CAUSE: u16 = 1;
+const UNRECOGNIZED_CHUNK_TYPE_CAUSE: u16 = 6;
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |     Cause Code = 1            |       Cause Length = 8        |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |        Stream Identifier      |         (Reserved)            |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+#[derive(Clone, Debug)]
+pub struct InvalidStreamId {
+    header: CauseHeader,
+    id: u16,
+    reserved: u16,
+}
+
+impl InvalidStreamId {
+    pub fn new(id: u16) -> Self {
+        Self {
+            header: CauseHeader {
+                code: INVALID_STREAM_ID_CAUSE,
+                len: 8,
+            },
+            id,
+            reserved: 0,
+        }
+    }
+
+    /// Returns the stream id the peer sent DATA on
+    pub fn stream_id(&self) -> u16 {
+        self.id
+    }
+}
+
+impl From<&[u8]> for InvalidStreamId {
+    fn from(buf: &[u8]) -> Self {
+        Self {
+            header: CauseHeader::parse(buf),
+            id: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[4..=5]).unwrap()),
+            reserved: 0,
+        }
+    }
+}
+
+impl Cause for InvalidStreamId {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.id.to_be_bytes());
+        v.extend(self.reserved.to_be_bytes());
+        v
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MissingMandatoryParam {
+    header: CauseHeader,
+    num: u32,
+    params: Vec<ParamType>,
+}
+
+impl Cause for MissingMandatoryParam {
+    fn get_bytes(&self) -> Vec<u8> {
+        todo!()
+    }
+}
+
+#[derive(Clone, Debug)]
 pub struct StateCookieError {
     header: CauseHeader,
     staleness_measure: u32,
@@ -850,14 +1684,95 @@ impl Cause for UnresolvableAddr {
     }
 }
 
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |     Cause Code = 6            |       Cause Length            |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       /                  Unrecognized Chunk                           /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
 #[derive(Clone, Debug)]
 pub struct UnrecognizedChunkType {
     header: CauseHeader,
+    chunk: Vec<u8>,
+}
+
+impl UnrecognizedChunkType {
+    /// Creates the cause reporting the given chunk, header included
+    pub fn new(chunk: Vec<u8>) -> Self {
+        Self {
+            header: CauseHeader {
+                code: UNRECOGNIZED_CHUNK_TYPE_CAUSE,
+                len: 4 + chunk.len() as u16,
+            },
+            chunk,
+        }
+    }
+
+    /// Returns the chunk the peer didn't recognize
+    pub fn chunk(&self) -> &[u8] {
+        &self.chunk
+    }
+}
+
+impl From<&[u8]> for UnrecognizedChunkType {
+    fn from(buf: &[u8]) -> Self {
+        let header = CauseHeader::parse(buf);
+        let chunk = buf[4..header.len as usize].to_vec();
+        Self { header, chunk }
+    }
 }
 
 impl Cause for UnrecognizedChunkType {
     fn get_bytes(&self) -> Vec<u8> {
-        todo!()
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(&self.chunk);
+        // the next cause starts 4 byte aligned
+        v.resize(v.len() + padding(self.chunk.len()), 0);
+        v
+    }
+}
+
+/// A cause that isn't parsed, kept as it was recvd so the chunk carrying it
+/// can be encoded again
+#[derive(Clone, Debug)]
+pub struct UnparsedCause {
+    header: CauseHeader,
+    value: Vec<u8>,
+}
+
+impl UnparsedCause {
+    pub fn code(&self) -> u16 {
+        self.header.code
+    }
+
+    /// Returns the cause specific information
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl From<&[u8]> for UnparsedCause {
+    fn from(buf: &[u8]) -> Self {
+        let header = CauseHeader::parse(buf);
+        let value = buf[4..header.len as usize].to_vec();
+        Self { header, value }
+    }
+}
+
+impl Cause for UnparsedCause {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(&self.value);
+        // the next cause starts 4 byte aligned
+        v.resize(v.len() + padding(self.value.len()), 0);
+        v
     }
 }
 
@@ -945,9 +1860,61 @@ pub struct Error {
     errors: Vec<Box<dyn Cause>>,
 }
 
+impl Error {
+    pub fn new(errors: Vec<Box<dyn Cause>>) -> Self {
+        let len: usize = errors.iter().map(|e| e.get_bytes().len()).sum();
+        Self {
+            header: ChunkHeader::new(9, 0, 4 + len as u16),
+            errors,
+        }
+    }
+
+    /// Returns the error causes carried by this chunk
+    pub fn errors(&self) -> &[Box<dyn Cause>] {
+        &self.errors
+    }
+}
+
+impl From<&[u8]> for Error {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = (header.length() as usize).min(buf.len());
+        let mut errors: Vec<Box<dyn Cause>> = vec![];
+
+        let mut offset = 4;
+        while offset + 4 <= len {
+            let cause_header = CauseHeader::parse(&buf[offset..]);
+            let cause_len = cause_header.len as usize;
+            if cause_len < 4 || offset + cause_len > len {
+                break;
+            }
+            match cause_header.code {
+                INVALID_STREAM_ID_CAUSE if cause_len == 8 => errors.push(Box::new(
+                    InvalidStreamId::from(&buf[offset..offset + cause_len]),
+                )),
+                UNRECOGNIZED_CHUNK_TYPE_CAUSE => errors.push(Box::new(
+                    UnrecognizedChunkType::from(&buf[offset..offset + cause_len]),
+                )),
+                // TODO abhi - parse other causes as and when required
+                _ => errors.push(Box::new(UnparsedCause::from(
+                    &buf[offset..offset + cause_len],
+                ))),
+            }
+            offset += cause_len + padding(cause_len);
+        }
+
+        Self { header, errors }
+    }
+}
+
 impl Chunk for Error {
     fn get_bytes(&self) -> Vec<u8> {
-        todo!()
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        for error in &self.errors {
+            v.extend(error.get_bytes());
+        }
+        v
     }
 
     fn chunk_type(&self) -> ChunkType {
@@ -1095,3 +2062,1090 @@ impl Chunk for ShutdownComplete {
         self.header.chunk_type.into()
     }
 }
+
+/// ASCONF related error causes (RFC 5061 section 4.2)
+pub const UNRESOLVABLE_ADDR_CAUSE: u16 = 5;
+pub const DELETE_LAST_ADDR_CAUSE: u16 = 0xa0;
+pub const RESOURCE_SHORTAGE_CAUSE: u16 = 0xa1;
+pub const DELETE_SOURCE_ADDR_CAUSE: u16 = 0xa2;
+pub const ILLEGAL_ASCONF_ACK_CAUSE: u16 = 0xa3;
+pub const NO_AUTHORIZATION_CAUSE: u16 = 0xa4;
+
+/// Operation requested by an ASCONF param
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AsconfOp {
+    AddIp,
+    DeleteIp,
+    SetPrimary,
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |        Type = 0xC001          |    Length = Variable          |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |               ASCONF-Request Correlation ID                   |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                       Address Parameter                       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+
+   Delete IP Address (0xC002) and Set Primary Address (0xC004) look the same
+*/
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsconfRequest {
+    pub op: AsconfOp,
+    pub correlation_id: u32,
+    pub addr: IpAddr,
+}
+
+impl From<&AsconfRequest> for Parameter {
+    fn from(request: &AsconfRequest) -> Self {
+        let param_type = match request.op {
+            AsconfOp::AddIp => ParamType::AddIpAddr,
+            AsconfOp::DeleteIp => ParamType::DeleteIpAddr,
+            AsconfOp::SetPrimary => ParamType::SetPrimaryAddr,
+        };
+        let mut value = request.correlation_id.to_be_bytes().to_vec();
+        value.extend(Vec::<u8>::from(&Parameter::from_ip(request.addr)));
+        Parameter::new(param_type, value)
+    }
+}
+
+impl TryFrom<&Parameter> for AsconfRequest {
+    type Error = ();
+
+    fn try_from(param: &Parameter) -> Result<Self, Self::Error> {
+        let op = match param.param_type {
+            ParamType::AddIpAddr => AsconfOp::AddIp,
+            ParamType::DeleteIpAddr => AsconfOp::DeleteIp,
+            ParamType::SetPrimaryAddr => AsconfOp::SetPrimary,
+            _ => return Err(()),
+        };
+        if param.value.len() < 8 {
+            return Err(());
+        }
+        let addr = parse_optional_params(&param.value, 4)
+            .and_then(|params| params.first().and_then(|param| param.ip()))
+            .ok_or(())?;
+        Ok(Self {
+            op,
+            correlation_id: u32::from_be_bytes(<[u8; 4]>::try_from(&param.value[0..4]).unwrap()),
+            addr,
+        })
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |      Type = 0xC003            |      Length = Variable        |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                  ASCONF-Request Correlation ID                |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                       Error Cause(s)                          |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+
+   a Success Indication (0xC005) only carries the correlation id
+*/
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AsconfResponse {
+    pub correlation_id: u32,
+    /// cause code of the error the request failed with, `None` on success
+    pub error: Option<u16>,
+}
+
+impl From<&AsconfResponse> for Parameter {
+    fn from(response: &AsconfResponse) -> Self {
+        let mut value = response.correlation_id.to_be_bytes().to_vec();
+        match response.error {
+            Some(code) => {
+                value.extend(<[u8; 4]>::from(&CauseHeader { code, len: 4 }));
+                Parameter::new(ParamType::ErrorCauseIndication, value)
+            }
+            None => Parameter::new(ParamType::SuccessIndication, value),
+        }
+    }
+}
+
+impl TryFrom<&Parameter> for AsconfResponse {
+    type Error = ();
+
+    fn try_from(param: &Parameter) -> Result<Self, Self::Error> {
+        if param.value.len() < 4 {
+            return Err(());
+        }
+        let correlation_id = u32::from_be_bytes(<[u8; 4]>::try_from(&param.value[0..4]).unwrap());
+        let error = match param.param_type {
+            ParamType::SuccessIndication => None,
+            ParamType::ErrorCauseIndication if param.value.len() >= 8 => {
+                Some(CauseHeader::parse(&param.value[4..]).code)
+            }
+            _ => return Err(()),
+        };
+        Ok(Self {
+            correlation_id,
+            error,
+        })
+    }
+}
+
+/// Returns the params of an ASCONF/ASCONF-ACK that follow the serial number
+fn parse_asconf_params(buf: &[u8]) -> Vec<Parameter> {
+    let len = (ChunkHeader::parse(buf).length() as usize).min(buf.len());
+    parse_optional_params(&buf[..len], 8).unwrap_or_default()
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Type = 0xC1   |  Chunk Flags  |      Chunk Length             |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                       Sequence Number                         |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                    Address Parameter                          |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                     ASCONF Parameter #1                       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /                             ....                              /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                     ASCONF Parameter #N                       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+#[derive(Clone, Debug)]
+pub struct Asconf {
+    header: ChunkHeader,
+    serial_no: u32,
+    addr: Option<IpAddr>,
+    requests: Vec<AsconfRequest>,
+}
+
+impl Asconf {
+    /// Creates an ASCONF sent from the given local addr
+    pub fn new(serial_no: u32, addr: IpAddr, requests: Vec<AsconfRequest>) -> Self {
+        let mut params = vec![Parameter::from_ip(addr)];
+        params.extend(requests.iter().map(Parameter::from));
+        Self {
+            header: ChunkHeader::new(0xc1, 0, 8 + params_len(&Some(params))),
+            serial_no,
+            addr: Some(addr),
+            requests,
+        }
+    }
+
+    pub fn serial_no(&self) -> u32 {
+        self.serial_no
+    }
+
+    /// Returns the local addr of the sender the ASCONF was sent from
+    pub fn addr(&self) -> Option<IpAddr> {
+        self.addr
+    }
+
+    pub fn requests(&self) -> &[AsconfRequest] {
+        &self.requests
+    }
+}
+
+impl From<&[u8]> for Asconf {
+    fn from(buf: &[u8]) -> Self {
+        let params = parse_asconf_params(buf);
+        Self {
+            header: ChunkHeader::parse(buf),
+            serial_no: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+            addr: params.first().and_then(|param| param.ip()),
+            // requests that can't be parsed are skipped
+            requests: params
+                .iter()
+                .skip(1)
+                .filter_map(|param| AsconfRequest::try_from(param).ok())
+                .collect(),
+        }
+    }
+}
+
+impl Chunk for Asconf {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.serial_no.to_be_bytes());
+        if let Some(addr) = self.addr {
+            v.extend(Vec::<u8>::from(&Parameter::from_ip(addr)));
+        }
+        for request in &self.requests {
+            v.extend(Vec::<u8>::from(&Parameter::from(request)));
+        }
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Type = 0x80   |  Chunk Flags  |      Chunk Length             |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                       Sequence Number                         |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                 ASCONF Parameter Response#1                   |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /                             ....                              /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                 ASCONF Parameter Response#N                   |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+#[derive(Clone, Debug)]
+pub struct AsconfAck {
+    header: ChunkHeader,
+    serial_no: u32,
+    responses: Vec<AsconfResponse>,
+}
+
+impl AsconfAck {
+    pub fn new(serial_no: u32, responses: Vec<AsconfResponse>) -> Self {
+        let params = responses.iter().map(Parameter::from).collect();
+        Self {
+            header: ChunkHeader::new(0x80, 0, 8 + params_len(&Some(params))),
+            serial_no,
+            responses,
+        }
+    }
+
+    pub fn serial_no(&self) -> u32 {
+        self.serial_no
+    }
+
+    pub fn responses(&self) -> &[AsconfResponse] {
+        &self.responses
+    }
+}
+
+impl From<&[u8]> for AsconfAck {
+    fn from(buf: &[u8]) -> Self {
+        Self {
+            header: ChunkHeader::parse(buf),
+            serial_no: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+            responses: parse_asconf_params(buf)
+                .iter()
+                .filter_map(|param| AsconfResponse::try_from(param).ok())
+                .collect(),
+        }
+    }
+}
+
+impl Chunk for AsconfAck {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.serial_no.to_be_bytes());
+        for response in &self.responses {
+            v.extend(Vec::<u8>::from(&Parameter::from(response)));
+        }
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Type = 0x0F   |   Flags=0     |             Length            |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |     Shared Key Identifier     |   HMAC Identifier             |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                                                               |
+       \                             HMAC                              /
+       /                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+#[derive(Clone, Debug)]
+pub struct Auth {
+    header: ChunkHeader,
+    key_id: u16,
+    hmac_id: u16,
+    hmac: Vec<u8>,
+}
+
+impl Auth {
+    pub fn new(key_id: u16, hmac_id: u16, hmac: Vec<u8>) -> Self {
+        Self {
+            header: ChunkHeader::new(15, 0, 8 + hmac.len() as u16),
+            key_id,
+            hmac_id,
+            hmac,
+        }
+    }
+
+    /// Returns the id of the endpoint pair shared key the HMAC was computed with
+    pub fn key_id(&self) -> u16 {
+        self.key_id
+    }
+
+    pub fn hmac_id(&self) -> u16 {
+        self.hmac_id
+    }
+
+    pub fn hmac(&self) -> &[u8] {
+        &self.hmac
+    }
+}
+
+impl From<&[u8]> for Auth {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = (header.length() as usize).clamp(8, buf.len());
+        Self {
+            header,
+            key_id: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[4..6]).unwrap()),
+            hmac_id: u16::from_be_bytes(<[u8; 2]>::try_from(&buf[6..8]).unwrap()),
+            hmac: buf[8..len].to_vec(),
+        }
+    }
+}
+
+impl Chunk for Auth {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.key_id.to_be_bytes());
+        v.extend(self.hmac_id.to_be_bytes());
+        v.extend(&self.hmac);
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |   Type = 192  |  Flags = 0x00 |        Length = Variable      |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                      New Cumulative TSN                       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |         Stream-1              |       Stream Sequence-1       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               /
+       /                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |         Stream-N              |       Stream Sequence-N       |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+/// Tells the peer to move its cumulative TSN forward past abandoned DATA (RFC 3758 section 3.2)
+#[derive(Clone, Debug)]
+pub struct ForwardTsn {
+    header: ChunkHeader,
+    new_cumulative_tsn: u32,
+    // the largest stream seq no skipped on each ordered stream
+    streams: Vec<(u16, u16)>,
+}
+
+impl ForwardTsn {
+    pub fn new(new_cumulative_tsn: u32, streams: Vec<(u16, u16)>) -> Self {
+        Self {
+            header: ChunkHeader::new(0xc0, 0, 8 + 4 * streams.len() as u16),
+            new_cumulative_tsn,
+            streams,
+        }
+    }
+
+    pub fn new_cumulative_tsn(&self) -> u32 {
+        self.new_cumulative_tsn
+    }
+
+    /// Returns the (stream id, stream seq no) pairs of the ordered msgs that were skipped
+    pub fn streams(&self) -> &[(u16, u16)] {
+        &self.streams
+    }
+}
+
+impl From<&[u8]> for ForwardTsn {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = (header.length() as usize).clamp(8, buf.len());
+        Self {
+            header,
+            new_cumulative_tsn: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+            // a trailing pair that isn't whole is ignored
+            streams: buf[8..len]
+                .chunks_exact(4)
+                .map(|s| {
+                    (
+                        u16::from_be_bytes(<[u8; 2]>::try_from(&s[..2]).unwrap()),
+                        u16::from_be_bytes(<[u8; 2]>::try_from(&s[2..]).unwrap()),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Chunk for ForwardTsn {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.new_cumulative_tsn.to_be_bytes());
+        for (stream_id, stream_seq_no) in &self.streams {
+            v.extend(stream_id.to_be_bytes());
+            v.extend(stream_seq_no.to_be_bytes());
+        }
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |   Type = 194  | Flags = 0x00  |      Length = Variable        |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                       New Cumulative TSN                      |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |     Stream Identifier         |          Reserved           |U|
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                       Message Identifier                      |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /                                                               /
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+/// A stream listed in an I-FORWARD-TSN along with the largest message id
+/// skipped on it, for either its ordered or its unordered msgs
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkippedMessages {
+    pub stream_id: u16,
+    pub unordered: bool,
+    pub message_id: u32,
+}
+
+/// Tells the peer to move its cumulative TSN forward past abandoned I-DATA
+/// (RFC 8260 section 2.3.1)
+#[derive(Clone, Debug)]
+pub struct IForwardTsn {
+    header: ChunkHeader,
+    new_cumulative_tsn: u32,
+    streams: Vec<SkippedMessages>,
+}
+
+impl IForwardTsn {
+    pub fn new(new_cumulative_tsn: u32, streams: Vec<SkippedMessages>) -> Self {
+        Self {
+            header: ChunkHeader::new(0xc2, 0, 8 + 8 * streams.len() as u16),
+            new_cumulative_tsn,
+            streams,
+        }
+    }
+
+    pub fn new_cumulative_tsn(&self) -> u32 {
+        self.new_cumulative_tsn
+    }
+
+    pub fn streams(&self) -> &[SkippedMessages] {
+        &self.streams
+    }
+}
+
+impl From<&[u8]> for IForwardTsn {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = (header.length() as usize).clamp(8, buf.len());
+        Self {
+            header,
+            new_cumulative_tsn: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+            streams: buf[8..len]
+                .chunks_exact(8)
+                .map(|s| SkippedMessages {
+                    stream_id: u16::from_be_bytes(<[u8; 2]>::try_from(&s[..2]).unwrap()),
+                    unordered: s[3] & 0x01 != 0,
+                    message_id: u32::from_be_bytes(<[u8; 4]>::try_from(&s[4..]).unwrap()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Chunk for IForwardTsn {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.new_cumulative_tsn.to_be_bytes());
+        for skipped in &self.streams {
+            v.extend(skipped.stream_id.to_be_bytes());
+            v.extend([0, skipped.unordered as u8]);
+            v.extend(skipped.message_id.to_be_bytes());
+        }
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |     Parameter Type = 13       | Parameter Length = 16 + 2 * N |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |           Re-configuration Request Sequence Number            |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |           Re-configuration Response Sequence Number           |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                Sender's Last Assigned TSN                     |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |  Stream Number 1 (optional)   |    Stream Number 2 (optional) |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       /                            ......                             /
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |  Stream Number N-1 (optional) |    Stream Number N (optional) |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+
+   an Incoming SSN Reset Request (14) only carries the request seq no and the streams,
+   an SSN/TSN Reset Request (15) only carries the request seq no.
+
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |     Parameter Type = 17       |      Parameter Length = 12    |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |          Re-configuration Request Sequence Number             |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |      Number of new streams    |         Reserved              |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+
+   an Add Incoming Streams Request (18) looks the same
+*/
+
+/// A stream reconfiguration request (RFC 6525 section 4).
+/// An empty list of streams stands for all the streams.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReconfigRequest {
+    /// resets the seq nos of the streams the sender sends on
+    OutgoingSsnReset {
+        seq_no: u32,
+        /// seq no of the Incoming SSN Reset Request this one answers, if any
+        response_seq_no: u32,
+        /// the streams are reset once the recvr got all the DATA up to this TSN
+        last_tsn: u32,
+        streams: Vec<u16>,
+    },
+    /// asks the recvr to reset the seq nos of the streams it sends on
+    IncomingSsnReset { seq_no: u32, streams: Vec<u16> },
+    /// resets the seq nos of all the streams and the TSNs in both directions
+    SsnTsnReset { seq_no: u32 },
+    /// adds streams the sender sends on
+    AddOutgoingStreams { seq_no: u32, num_streams: u16 },
+    /// asks the recvr to add streams it sends on
+    AddIncomingStreams { seq_no: u32, num_streams: u16 },
+}
+
+impl ReconfigRequest {
+    pub fn seq_no(&self) -> u32 {
+        match self {
+            ReconfigRequest::OutgoingSsnReset { seq_no, .. }
+            | ReconfigRequest::IncomingSsnReset { seq_no, .. }
+            | ReconfigRequest::SsnTsnReset { seq_no }
+            | ReconfigRequest::AddOutgoingStreams { seq_no, .. }
+            | ReconfigRequest::AddIncomingStreams { seq_no, .. } => *seq_no,
+        }
+    }
+
+    /// Returns the request with its seq no replaced
+    pub fn with_seq_no(mut self, new_seq_no: u32) -> Self {
+        match &mut self {
+            ReconfigRequest::OutgoingSsnReset { seq_no, .. }
+            | ReconfigRequest::IncomingSsnReset { seq_no, .. }
+            | ReconfigRequest::SsnTsnReset { seq_no }
+            | ReconfigRequest::AddOutgoingStreams { seq_no, .. }
+            | ReconfigRequest::AddIncomingStreams { seq_no, .. } => *seq_no = new_seq_no,
+        }
+        self
+    }
+}
+
+impl From<&ReconfigRequest> for Parameter {
+    fn from(request: &ReconfigRequest) -> Self {
+        let streams_value = |seq_no: u32, streams: &[u16]| {
+            let mut value = seq_no.to_be_bytes().to_vec();
+            value.extend(streams.iter().flat_map(|stream| stream.to_be_bytes()));
+            value
+        };
+        let add_streams_value = |seq_no: u32, num_streams: u16| {
+            let mut value = seq_no.to_be_bytes().to_vec();
+            value.extend(num_streams.to_be_bytes());
+            value.extend([0u8; 2]);
+            value
+        };
+        match request {
+            ReconfigRequest::OutgoingSsnReset {
+                seq_no,
+                response_seq_no,
+                last_tsn,
+                streams,
+            } => {
+                let mut value = seq_no.to_be_bytes().to_vec();
+                value.extend(response_seq_no.to_be_bytes());
+                value.extend(last_tsn.to_be_bytes());
+                value.extend(streams.iter().flat_map(|stream| stream.to_be_bytes()));
+                Parameter::new(ParamType::OutgoingSsnReset, value)
+            }
+            ReconfigRequest::IncomingSsnReset { seq_no, streams } => {
+                Parameter::new(ParamType::IncomingSsnReset, streams_value(*seq_no, streams))
+            }
+            ReconfigRequest::SsnTsnReset { seq_no } => {
+                Parameter::new(ParamType::SsnTsnReset, seq_no.to_be_bytes().to_vec())
+            }
+            ReconfigRequest::AddOutgoingStreams {
+                seq_no,
+                num_streams,
+            } => Parameter::new(
+                ParamType::AddOutgoingStreams,
+                add_streams_value(*seq_no, *num_streams),
+            ),
+            ReconfigRequest::AddIncomingStreams {
+                seq_no,
+                num_streams,
+            } => Parameter::new(
+                ParamType::AddIncomingStreams,
+                add_streams_value(*seq_no, *num_streams),
+            ),
+        }
+    }
+}
+
+impl TryFrom<&Parameter> for ReconfigRequest {
+    type Error = ();
+
+    fn try_from(param: &Parameter) -> Result<Self, Self::Error> {
+        let value = &param.value;
+        let u32_at = |offset: usize| {
+            value
+                .get(offset..offset + 4)
+                .map(|bytes| u32::from_be_bytes(<[u8; 4]>::try_from(bytes).unwrap()))
+                .ok_or(())
+        };
+        let streams_from = |offset: usize| -> Vec<u16> {
+            value
+                .get(offset..)
+                .unwrap_or_default()
+                .chunks_exact(2)
+                .map(|stream| u16::from_be_bytes(<[u8; 2]>::try_from(stream).unwrap()))
+                .collect()
+        };
+        let num_streams = || {
+            value
+                .get(4..6)
+                .map(|bytes| u16::from_be_bytes(<[u8; 2]>::try_from(bytes).unwrap()))
+                .ok_or(())
+        };
+        let seq_no = u32_at(0)?;
+        match param.param_type {
+            ParamType::OutgoingSsnReset => Ok(ReconfigRequest::OutgoingSsnReset {
+                seq_no,
+                response_seq_no: u32_at(4)?,
+                last_tsn: u32_at(8)?,
+                streams: streams_from(12),
+            }),
+            ParamType::IncomingSsnReset => Ok(ReconfigRequest::IncomingSsnReset {
+                seq_no,
+                streams: streams_from(4),
+            }),
+            ParamType::SsnTsnReset => Ok(ReconfigRequest::SsnTsnReset { seq_no }),
+            ParamType::AddOutgoingStreams => Ok(ReconfigRequest::AddOutgoingStreams {
+                seq_no,
+                num_streams: num_streams()?,
+            }),
+            ParamType::AddIncomingStreams => Ok(ReconfigRequest::AddIncomingStreams {
+                seq_no,
+                num_streams: num_streams()?,
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Result of a stream reconfiguration request (RFC 6525 section 4.4)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReconfigResult {
+    SuccessNothingToDo,
+    SuccessPerformed,
+    Denied,
+    ErrorWrongSsn,
+    ErrorRequestAlreadyInProgress,
+    ErrorBadSequenceNumber,
+    InProgress,
+}
+
+impl ReconfigResult {
+    pub fn is_success(self) -> bool {
+        matches!(
+            self,
+            ReconfigResult::SuccessNothingToDo | ReconfigResult::SuccessPerformed
+        )
+    }
+}
+
+impl From<ReconfigResult> for u32 {
+    fn from(result: ReconfigResult) -> Self {
+        match result {
+            ReconfigResult::SuccessNothingToDo => 0,
+            ReconfigResult::SuccessPerformed => 1,
+            ReconfigResult::Denied => 2,
+            ReconfigResult::ErrorWrongSsn => 3,
+            ReconfigResult::ErrorRequestAlreadyInProgress => 4,
+            ReconfigResult::ErrorBadSequenceNumber => 5,
+            ReconfigResult::InProgress => 6,
+        }
+    }
+}
+
+impl TryFrom<u32> for ReconfigResult {
+    type Error = ();
+
+    fn try_from(result: u32) -> Result<Self, Self::Error> {
+        match result {
+            0 => Ok(ReconfigResult::SuccessNothingToDo),
+            1 => Ok(ReconfigResult::SuccessPerformed),
+            2 => Ok(ReconfigResult::Denied),
+            3 => Ok(ReconfigResult::ErrorWrongSsn),
+            4 => Ok(ReconfigResult::ErrorRequestAlreadyInProgress),
+            5 => Ok(ReconfigResult::ErrorBadSequenceNumber),
+            6 => Ok(ReconfigResult::InProgress),
+            _ => Err(()),
+        }
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |     Parameter Type = 16       |      Parameter Length         |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |         Re-configuration Response Sequence Number             |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                            Result                             |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                   Sender's Next TSN (optional)                |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                  Receiver's Next TSN (optional)               |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReconfigResponse {
+    /// seq no of the request being answered
+    pub seq_no: u32,
+    pub result: ReconfigResult,
+    /// the next TSN the sender of the response sends and the next TSN it expects,
+    /// only present in the response to an SSN/TSN Reset Request
+    pub next_tsns: Option<(u32, u32)>,
+}
+
+impl From<&ReconfigResponse> for Parameter {
+    fn from(response: &ReconfigResponse) -> Self {
+        let mut value = response.seq_no.to_be_bytes().to_vec();
+        value.extend(u32::from(response.result).to_be_bytes());
+        if let Some((senders_next_tsn, receivers_next_tsn)) = response.next_tsns {
+            value.extend(senders_next_tsn.to_be_bytes());
+            value.extend(receivers_next_tsn.to_be_bytes());
+        }
+        Parameter::new(ParamType::ReconfigResponse, value)
+    }
+}
+
+impl TryFrom<&Parameter> for ReconfigResponse {
+    type Error = ();
+
+    fn try_from(param: &Parameter) -> Result<Self, Self::Error> {
+        if param.param_type != ParamType::ReconfigResponse || param.value.len() < 8 {
+            return Err(());
+        }
+        let u32_at = |offset: usize| {
+            u32::from_be_bytes(<[u8; 4]>::try_from(&param.value[offset..offset + 4]).unwrap())
+        };
+        Ok(Self {
+            seq_no: u32_at(0),
+            result: ReconfigResult::try_from(u32_at(4))?,
+            next_tsns: if param.value.len() >= 16 {
+                Some((u32_at(8), u32_at(12)))
+            } else {
+                None
+            },
+        })
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Type = 130    |  Chunk Flags  |      Chunk Length             |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /                  Re-configuration Parameter                   /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /             Re-configuration Parameter (optional)             /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+/// Carries stream reconfiguration requests and responses (RFC 6525 section 3.1).
+/// The responses go in front of the requests.
+#[derive(Clone, Debug)]
+pub struct ReConfig {
+    header: ChunkHeader,
+    responses: Vec<ReconfigResponse>,
+    requests: Vec<ReconfigRequest>,
+}
+
+impl ReConfig {
+    pub fn new(responses: Vec<ReconfigResponse>, requests: Vec<ReconfigRequest>) -> Self {
+        let params = responses
+            .iter()
+            .map(Parameter::from)
+            .chain(requests.iter().map(Parameter::from))
+            .collect();
+        Self {
+            header: ChunkHeader::new(0x82, 0, 4 + params_len(&Some(params))),
+            responses,
+            requests,
+        }
+    }
+
+    pub fn responses(&self) -> &[ReconfigResponse] {
+        &self.responses
+    }
+
+    pub fn requests(&self) -> &[ReconfigRequest] {
+        &self.requests
+    }
+}
+
+impl From<&[u8]> for ReConfig {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let len = (header.length() as usize).min(buf.len());
+        let params = parse_optional_params(&buf[..len], 4).unwrap_or_default();
+        Self {
+            header,
+            // params that can't be parsed are skipped
+            responses: params
+                .iter()
+                .filter_map(|param| ReconfigResponse::try_from(param).ok())
+                .collect(),
+            requests: params
+                .iter()
+                .filter_map(|param| ReconfigRequest::try_from(param).ok())
+                .collect(),
+        }
+    }
+}
+
+impl Chunk for ReConfig {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        for response in &self.responses {
+            v.extend(Vec::<u8>::from(&Parameter::from(response)));
+        }
+        for request in &self.requests {
+            v.extend(Vec::<u8>::from(&Parameter::from(request)));
+        }
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Chunk Type=12 | Flags=00000000|    Chunk Length = 8           |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                      Lowest TSN Number                        |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+/// Tells the peer that DATA it sent arrived with the CE codepoint set (RFC 4960 appendix A)
+#[derive(Clone, Debug)]
+pub struct Ecne {
+    header: ChunkHeader,
+    lowest_tsn: u32,
+}
+
+impl Ecne {
+    pub fn new(lowest_tsn: u32) -> Self {
+        Self {
+            header: ChunkHeader::new(12, 0, 8),
+            lowest_tsn,
+        }
+    }
+
+    /// Returns the TSN of the DATA that arrived with the CE codepoint set
+    pub fn lowest_tsn(&self) -> u32 {
+        self.lowest_tsn
+    }
+}
+
+impl From<&[u8]> for Ecne {
+    fn from(buf: &[u8]) -> Self {
+        Self {
+            header: ChunkHeader::parse(buf),
+            lowest_tsn: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+        }
+    }
+}
+
+impl Chunk for Ecne {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.lowest_tsn.to_be_bytes());
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Chunk Type=13 | Flags=00000000|    Chunk Length = 8           |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       |                      Lowest TSN Number                        |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+/// Tells the peer that the cwnd was reduced in response to its ECNE (RFC 4960 appendix A)
+#[derive(Clone, Debug)]
+pub struct Cwr {
+    header: ChunkHeader,
+    lowest_tsn: u32,
+}
+
+impl Cwr {
+    pub fn new(lowest_tsn: u32) -> Self {
+        Self {
+            header: ChunkHeader::new(13, 0, 8),
+            lowest_tsn,
+        }
+    }
+
+    /// Returns the TSN up to which ECNEs are answered by this CWR
+    pub fn lowest_tsn(&self) -> u32 {
+        self.lowest_tsn
+    }
+}
+
+impl From<&[u8]> for Cwr {
+    fn from(buf: &[u8]) -> Self {
+        Self {
+            header: ChunkHeader::parse(buf),
+            lowest_tsn: u32::from_be_bytes(<[u8; 4]>::try_from(&buf[4..8]).unwrap()),
+        }
+    }
+}
+
+impl Chunk for Cwr {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(self.lowest_tsn.to_be_bytes());
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
+
+/*
+        0                   1                   2                   3
+        0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       | Type = 0x84   |   Flags=0     |             Length            |
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+       \                                                               \
+       /                          Padding Data                         /
+       \                                                               \
+       +-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+-+
+*/
+
+/// Pads a packet to a larger size (RFC 4820). The padding data is ignored by the recvr.
+#[derive(Clone, Debug)]
+pub struct Pad {
+    header: ChunkHeader,
+    padding: Vec<u8>,
+}
+
+impl Pad {
+    /// Creates a PAD chunk with `len` bytes of padding data
+    pub fn new(len: usize) -> Self {
+        Self {
+            header: ChunkHeader::new(0x84, 0, 4 + len as u16),
+            padding: vec![0; len],
+        }
+    }
+
+    /// Returns the number of bytes of padding data
+    pub fn padding_len(&self) -> usize {
+        self.padding.len()
+    }
+}
+
+impl From<&[u8]> for Pad {
+    fn from(buf: &[u8]) -> Self {
+        let header = ChunkHeader::parse(buf);
+        let padding = buf[4..header.length() as usize].to_vec();
+        Self { header, padding }
+    }
+}
+
+impl Chunk for Pad {
+    fn get_bytes(&self) -> Vec<u8> {
+        let mut v = vec![];
+        v.extend(<[u8; 4]>::from(&self.header));
+        v.extend(&self.padding);
+        v
+    }
+
+    fn chunk_type(&self) -> ChunkType {
+        self.header.chunk_type.into()
+    }
+}
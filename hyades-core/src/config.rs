@@ -0,0 +1,143 @@
+use crate::congestion::CongestionAlgorithm;
+use crate::heartbeat::HB_INTERVAL;
+use crate::pmtud::MAX_PLPMTU;
+use crate::rto::{RTO_INITIAL, RTO_MAX, RTO_MIN};
+use crate::scheduler::StreamScheduler;
+use std::time::Duration;
+
+const DEFAULT_RECV_BUFFER_SIZE: u32 = 65536;
+const DEFAULT_MAX_MESSAGE_SIZE: usize = 262144;
+const DEFAULT_NUM_OUTBOUND_STREAMS: u16 = 10;
+const DEFAULT_MAX_INBOUND_STREAMS: u16 = 65535;
+const ASSOCIATION_MAX_RETRANS: u32 = 10;
+const PATH_MAX_RETRANS: u32 = 5;
+// RFC 7829 section 5: a path is potentially failed on its first error
+const PATH_PF_MAX_RETRANS: u32 = 0;
+
+/// Tunable parameters of an association.
+/// These are set by the ULP before the association is created.
+#[derive(Clone, Debug)]
+pub struct AssociationConfig {
+    /// congestion control algorithm used by the association
+    pub congestion_algorithm: CongestionAlgorithm,
+    /// RTO used until the first RTT measurement is made
+    pub rto_initial: Duration,
+    /// lower bound of the RTO
+    pub rto_min: Duration,
+    /// upper bound of the RTO
+    pub rto_max: Duration,
+    /// size in bytes of the buffer holding received data until the ULP reads it
+    pub recv_buffer_size: u32,
+    /// largest user msg that can be sent or recvd
+    pub max_message_size: usize,
+    /// bytes of user data a send may leave buffered without waiting for them to be acked.
+    /// 0 makes every send wait until all the data sent so far is acked
+    pub send_buffer_size: usize,
+    /// number of outbound streams requested in the INIT/INIT ACK
+    pub num_outbound_streams: u16,
+    /// max number of inbound streams the peer may open
+    pub max_inbound_streams: u16,
+    /// interval between heartbeats on an idle path
+    pub heartbeat_interval: Duration,
+    /// errors after which the association fails
+    pub association_max_retrans: u32,
+    /// errors after which a path is inactive
+    pub path_max_retrans: u32,
+    /// errors after which a path is potentially failed.
+    /// the PF state is disabled if this isn't less than `path_max_retrans`
+    pub path_pf_max_retrans: u32,
+    /// switch back to the primary set by the ULP once it is active again
+    /// after the association failed over to another destination
+    pub failback: bool,
+    /// negotiate SCTP-AUTH with the peer
+    pub auth_enabled: bool,
+    /// chunk types we only accept from the peer when they are authenticated.
+    /// ASCONF and ASCONF-ACK are always authenticated when AUTH is enabled
+    pub auth_chunks: Vec<u8>,
+    /// negotiate user msg interleaving with the peer so msgs are sent in I-DATA chunks
+    pub interleaving_enabled: bool,
+    /// scheduler picking the stream the next DATA chunk is sent on
+    pub stream_scheduler: StreamScheduler,
+    /// negotiate ECN with the peer so congested routers can mark packets instead of dropping them
+    pub ecn_enabled: bool,
+    /// negotiate NR-SACK with the peer so acked DATA it won't renege on is freed early
+    pub nr_sack_enabled: bool,
+    /// probe every path with padded heartbeats to find the largest packet size it carries
+    pub plpmtud_enabled: bool,
+    /// largest packet size probed for, used as the PMTU of every path if PLPMTUD is disabled
+    pub max_plpmtu: u32,
+    /// pad every packet sent with a PAD chunk so the IP packet is at least this many bytes,
+    /// up to the PMTU, hiding the size of small msgs. 0 disables padding
+    pub min_packet_size: u32,
+    /// error detection method, such as `EDMID_DTLS`, that the lower layer provides in place
+    /// of the CRC32c. It is announced to the peer, after which packets with a zero checksum
+    /// are accepted. Packets are sent with a zero checksum if the peer announces the same
+    /// method. None always sends and checks the CRC32c
+    pub zero_checksum_edmid: Option<u32>,
+    /// adaptation code point sent to the peer in our INIT/INIT ACK.
+    /// the peer's one is passed on to the ULP as a notification. None sends none
+    pub adaptation_code_point: Option<u32>,
+}
+
+impl Default for AssociationConfig {
+    fn default() -> Self {
+        Self {
+            congestion_algorithm: CongestionAlgorithm::default(),
+            rto_initial: RTO_INITIAL,
+            rto_min: RTO_MIN,
+            rto_max: RTO_MAX,
+            recv_buffer_size: DEFAULT_RECV_BUFFER_SIZE,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            send_buffer_size: 0,
+            num_outbound_streams: DEFAULT_NUM_OUTBOUND_STREAMS,
+            max_inbound_streams: DEFAULT_MAX_INBOUND_STREAMS,
+            heartbeat_interval: HB_INTERVAL,
+            association_max_retrans: ASSOCIATION_MAX_RETRANS,
+            path_max_retrans: PATH_MAX_RETRANS,
+            path_pf_max_retrans: PATH_PF_MAX_RETRANS,
+            failback: true,
+            auth_enabled: false,
+            auth_chunks: vec![],
+            interleaving_enabled: false,
+            stream_scheduler: StreamScheduler::default(),
+            ecn_enabled: true,
+            nr_sack_enabled: false,
+            plpmtud_enabled: true,
+            max_plpmtu: MAX_PLPMTU,
+            min_packet_size: 0,
+            zero_checksum_edmid: None,
+            adaptation_code_point: None,
+        }
+    }
+}
+
+/// Per msg parameters of a send
+#[derive(Clone, Debug, Default)]
+pub struct SendOptions {
+    /// stream the msg is sent on
+    pub stream_id: u16,
+    /// deliver the msg as soon as it arrives instead of in stream seq no order
+    pub unordered: bool,
+    /// payload protocol identifier passed on to the peer's ULP as is
+    pub ppid: u32,
+    /// when the msg may be abandoned instead of being retransmitted
+    pub pr_policy: PrPolicy,
+}
+
+/// Partial reliability policy of a msg (RFC 3758, RFC 7496).
+/// Msgs are only abandoned if the peer supports PR-SCTP,
+/// otherwise they are sent reliably.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PrPolicy {
+    /// the msg is retransmitted until it is acked
+    #[default]
+    Reliable,
+    /// the msg is abandoned if it isn't acked within the given lifetime
+    Timed(Duration),
+    /// the msg is abandoned once it has been retransmitted this many times
+    MaxRetransmissions(u32),
+    /// the msg is abandoned to make room for a msg of a higher priority
+    /// when the send buffer is full. a lower value is a higher priority.
+    /// needs a `send_buffer_size` larger than 0
+    Priority(u16),
+}
@@ -0,0 +1,409 @@
+use std::cmp;
+use std::time::{Duration, Instant};
+
+/// A congestion control algorithm driving the cwnd of a path.
+/// All sizes are in bytes.
+pub trait CongestionControl: Send {
+    /// Returns the current congestion window
+    fn cwnd(&self) -> u32;
+
+    /// Returns the current slow start threshold
+    fn ssthresh(&self) -> u32;
+
+    /// Called when a SACK acknowledges `bytes_acked` new bytes.
+    /// `flight_size` is the number of bytes outstanding before the SACK arrived
+    /// and `rtt` is the round trip time sample taken from this SACK, if any.
+    fn on_ack(&mut self, bytes_acked: u32, flight_size: u32, rtt: Option<Duration>);
+
+    /// Called when the T3-rtx timer expires
+    fn on_timeout(&mut self);
+
+    /// Called when the peer echoes a CE mark, which ECN treats like a loss
+    fn on_loss(&mut self, flight_size: u32);
+
+    /// Called when the path MTU changes
+    fn set_mtu(&mut self, mtu: u32);
+}
+
+/// The congestion control algorithms available to an association
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CongestionAlgorithm {
+    /// The algorithm described in RFC 9260 section 7.2
+    #[default]
+    Standard,
+    /// CUBIC (RFC 9438), suited to high bandwidth-delay product paths
+    Cubic,
+    /// A BBR-style controller that sizes cwnd from the measured delivery rate and min RTT
+    Bbr,
+}
+
+impl CongestionAlgorithm {
+    /// Creates a controller of this kind for a path with the given MTU
+    pub fn build(&self, mtu: u32) -> Box<dyn CongestionControl> {
+        match self {
+            CongestionAlgorithm::Standard => Box::new(Standard::new(mtu)),
+            CongestionAlgorithm::Cubic => Box::new(Cubic::new(mtu)),
+            CongestionAlgorithm::Bbr => Box::new(Bbr::new(mtu)),
+        }
+    }
+}
+
+// 7.2.1; initial value of ssthreshold can be anything
+const INITIAL_SSTHRESH: u32 = 10000;
+
+/// 7.2.1: cwnd should be min(4*MTU, max (2*MTU, 4380 bytes))
+fn initial_cwnd(mtu: u32) -> u32 {
+    cmp::min(4 * mtu, cmp::max(2 * mtu, 4380))
+}
+
+/// Congestion control as described in RFC 9260 section 7.2
+pub struct Standard {
+    mtu: u32,
+    cwnd: u32,
+    ssthresh: u32,
+    partial_bytes_acked: u32,
+}
+
+impl Standard {
+    pub fn new(mtu: u32) -> Self {
+        Self {
+            mtu,
+            cwnd: initial_cwnd(mtu),
+            ssthresh: INITIAL_SSTHRESH,
+            partial_bytes_acked: 0,
+        }
+    }
+}
+
+impl CongestionControl for Standard {
+    fn cwnd(&self) -> u32 {
+        self.cwnd
+    }
+
+    fn ssthresh(&self) -> u32 {
+        self.ssthresh
+    }
+
+    fn on_ack(&mut self, bytes_acked: u32, flight_size: u32, _rtt: Option<Duration>) {
+        if self.cwnd <= self.ssthresh {
+            // 7.2.1: slow start. cwnd is only increased if it is being fully utilized
+            if flight_size >= self.cwnd {
+                self.cwnd += cmp::min(bytes_acked, self.mtu);
+            }
+        } else {
+            // 7.2.2: congestion avoidance
+            self.partial_bytes_acked += bytes_acked;
+            if self.partial_bytes_acked >= self.cwnd && flight_size >= self.cwnd {
+                self.partial_bytes_acked -= self.cwnd;
+                self.cwnd += self.mtu;
+            }
+        }
+    }
+
+    fn on_timeout(&mut self) {
+        // 7.2.3
+        self.ssthresh = cmp::max(self.cwnd / 2, 4 * self.mtu);
+        self.cwnd = self.mtu;
+        self.partial_bytes_acked = 0;
+    }
+
+    fn on_loss(&mut self, _flight_size: u32) {
+        // 7.2.3
+        self.ssthresh = cmp::max(self.cwnd / 2, 4 * self.mtu);
+        self.cwnd = self.ssthresh;
+        self.partial_bytes_acked = 0;
+    }
+
+    fn set_mtu(&mut self, mtu: u32) {
+        self.mtu = mtu;
+    }
+}
+
+// RFC 9438 section 5: constants of the cubic function
+const CUBIC_C: f64 = 0.4;
+const CUBIC_BETA: f64 = 0.7;
+
+/// CUBIC congestion control (RFC 9438).
+/// Windows are tracked in units of MTU sized segments internally.
+pub struct Cubic {
+    mtu: u32,
+    cwnd: u32,
+    ssthresh: u32,
+    // window size just before the last reduction, in segments
+    w_max: f64,
+    // time it takes the cubic function to grow back to `w_max`
+    k: f64,
+    // reno-friendly window estimate, in segments
+    w_est: f64,
+    epoch_start: Option<Instant>,
+    last_rtt: Duration,
+}
+
+impl Cubic {
+    pub fn new(mtu: u32) -> Self {
+        Self {
+            mtu,
+            cwnd: initial_cwnd(mtu),
+            ssthresh: INITIAL_SSTHRESH,
+            w_max: 0.0,
+            k: 0.0,
+            w_est: 0.0,
+            epoch_start: None,
+            last_rtt: Duration::from_millis(100),
+        }
+    }
+
+    fn segments(&self) -> f64 {
+        self.cwnd as f64 / self.mtu as f64
+    }
+
+    /// Reduces the window by the multiplicative decrease factor and starts a new epoch
+    fn reduce(&mut self) {
+        let segments = self.segments();
+        // section 4.7: fast convergence
+        self.w_max = if segments < self.w_max {
+            segments * (1.0 + CUBIC_BETA) / 2.0
+        } else {
+            segments
+        };
+        self.ssthresh = cmp::max((self.cwnd as f64 * CUBIC_BETA) as u32, 2 * self.mtu);
+        self.epoch_start = None;
+    }
+}
+
+impl CongestionControl for Cubic {
+    fn cwnd(&self) -> u32 {
+        self.cwnd
+    }
+
+    fn ssthresh(&self) -> u32 {
+        self.ssthresh
+    }
+
+    fn on_ack(&mut self, bytes_acked: u32, flight_size: u32, rtt: Option<Duration>) {
+        if let Some(rtt) = rtt {
+            self.last_rtt = rtt;
+        }
+
+        if flight_size < self.cwnd {
+            // the window isn't being fully utilized; don't grow it
+            return;
+        }
+
+        if self.cwnd <= self.ssthresh {
+            self.cwnd += cmp::min(bytes_acked, self.mtu);
+            return;
+        }
+
+        let now = Instant::now();
+        let epoch_start = match self.epoch_start {
+            Some(epoch_start) => epoch_start,
+            None => {
+                let segments = self.segments();
+                if self.w_max < segments {
+                    self.w_max = segments;
+                }
+                self.k = (self.w_max * (1.0 - CUBIC_BETA) / CUBIC_C).cbrt();
+                self.w_est = segments;
+                self.epoch_start = Some(now);
+                now
+            }
+        };
+
+        let segments = self.segments();
+        let t = (now - epoch_start + self.last_rtt).as_secs_f64();
+        let w_cubic = CUBIC_C * (t - self.k).powi(3) + self.w_max;
+
+        // section 4.3: reno-friendly region
+        let acked_segments = bytes_acked as f64 / self.mtu as f64;
+        self.w_est += 3.0 * (1.0 - CUBIC_BETA) / (1.0 + CUBIC_BETA) * acked_segments / segments;
+
+        let target = if w_cubic < self.w_est {
+            self.w_est
+        } else {
+            // section 4.4: the target is capped at 1.5 times the current window
+            w_cubic.min(1.5 * segments).max(segments)
+        };
+
+        let increase = ((target - segments) / segments * self.mtu as f64) as u32;
+        self.cwnd += cmp::min(increase, self.mtu);
+    }
+
+    fn on_timeout(&mut self) {
+        self.reduce();
+        self.cwnd = self.mtu;
+    }
+
+    fn on_loss(&mut self, _flight_size: u32) {
+        self.reduce();
+        self.cwnd = self.ssthresh;
+    }
+
+    fn set_mtu(&mut self, mtu: u32) {
+        self.mtu = mtu;
+    }
+}
+
+// gain used while probing for bandwidth during startup (2/ln2)
+const BBR_STARTUP_GAIN: f64 = 2.885;
+const BBR_CWND_GAIN: f64 = 2.0;
+// gains cycled through once the bottleneck bandwidth has been found
+const BBR_PROBE_GAINS: [f64; 8] = [1.25, 0.75, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+// number of delivery rate samples the max filter remembers
+const BBR_BW_WINDOW: usize = 10;
+// how long a min rtt sample stays valid
+const BBR_MIN_RTT_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BbrState {
+    Startup,
+    Drain,
+    ProbeBw,
+}
+
+/// A delay based controller in the style of BBR.
+/// cwnd is sized from the bandwidth-delay product measured on the path
+/// instead of being cut back on every loss.
+pub struct Bbr {
+    mtu: u32,
+    cwnd: u32,
+    state: BbrState,
+    // recent delivery rate samples in bytes/sec
+    bw_samples: Vec<f64>,
+    min_rtt: Option<(Duration, Instant)>,
+    // max bandwidth seen when startup last made progress
+    full_bw: f64,
+    full_bw_rounds: u8,
+    cycle_index: usize,
+    // total bytes delivered, used to tell when a round trip has passed
+    delivered: u64,
+    // the round ends once `delivered` reaches this
+    next_round_delivered: u64,
+}
+
+impl Bbr {
+    pub fn new(mtu: u32) -> Self {
+        Self {
+            mtu,
+            cwnd: initial_cwnd(mtu),
+            state: BbrState::Startup,
+            bw_samples: Vec::with_capacity(BBR_BW_WINDOW),
+            min_rtt: None,
+            full_bw: 0.0,
+            full_bw_rounds: 0,
+            cycle_index: 0,
+            delivered: 0,
+            next_round_delivered: 0,
+        }
+    }
+
+    /// Returns the phase the controller is currently in
+    pub fn state(&self) -> BbrState {
+        self.state
+    }
+
+    fn max_bw(&self) -> f64 {
+        self.bw_samples.iter().cloned().fold(0.0, f64::max)
+    }
+
+    fn gain(&self) -> f64 {
+        match self.state {
+            BbrState::Startup => BBR_STARTUP_GAIN,
+            BbrState::Drain => 1.0 / BBR_STARTUP_GAIN,
+            BbrState::ProbeBw => BBR_PROBE_GAINS[self.cycle_index],
+        }
+    }
+
+    /// Returns true if this ack ends a round trip, i.e. everything that was in flight
+    /// when the last round ended has been delivered
+    fn round_ended(&mut self, bytes_acked: u32, flight_size: u32) -> bool {
+        self.delivered += bytes_acked as u64;
+        if self.delivered < self.next_round_delivered {
+            return false;
+        }
+        self.next_round_delivered = self.delivered + flight_size.saturating_sub(bytes_acked) as u64;
+        true
+    }
+
+    /// Startup is over once the bandwidth stops growing by 25% for 3 rounds
+    fn check_full_bw(&mut self) {
+        let max_bw = self.max_bw();
+        if max_bw >= self.full_bw * 1.25 {
+            self.full_bw = max_bw;
+            self.full_bw_rounds = 0;
+        } else {
+            self.full_bw_rounds += 1;
+        }
+    }
+}
+
+impl CongestionControl for Bbr {
+    fn cwnd(&self) -> u32 {
+        self.cwnd
+    }
+
+    fn ssthresh(&self) -> u32 {
+        // bbr has no notion of a slow start threshold
+        u32::MAX
+    }
+
+    fn on_ack(&mut self, bytes_acked: u32, flight_size: u32, rtt: Option<Duration>) {
+        let rtt = match rtt {
+            Some(rtt) if !rtt.is_zero() => rtt,
+            // without an rtt sample we have nothing to build a model from
+            _ => return,
+        };
+
+        let now = Instant::now();
+        match self.min_rtt {
+            Some((min_rtt, at)) if rtt > min_rtt && now - at < BBR_MIN_RTT_WINDOW => {}
+            _ => self.min_rtt = Some((rtt, now)),
+        }
+
+        if self.bw_samples.len() == BBR_BW_WINDOW {
+            self.bw_samples.remove(0);
+        }
+        self.bw_samples.push(bytes_acked as f64 / rtt.as_secs_f64());
+
+        let min_rtt = self.min_rtt.unwrap().0.as_secs_f64();
+        let bdp = self.max_bw() * min_rtt;
+        let round_start = self.round_ended(bytes_acked, flight_size);
+
+        match self.state {
+            BbrState::Startup if round_start => {
+                self.check_full_bw();
+                if self.full_bw_rounds >= 3 {
+                    self.state = BbrState::Drain;
+                }
+            }
+            BbrState::Drain if (flight_size as f64) <= bdp => {
+                self.state = BbrState::ProbeBw;
+                self.cycle_index = 0;
+            }
+            BbrState::ProbeBw if round_start => {
+                self.cycle_index = (self.cycle_index + 1) % BBR_PROBE_GAINS.len();
+            }
+            _ => {}
+        }
+
+        let gain = if self.state == BbrState::Startup {
+            BBR_STARTUP_GAIN
+        } else {
+            BBR_CWND_GAIN * self.gain()
+        };
+        self.cwnd = cmp::max((bdp * gain) as u32, 4 * self.mtu);
+    }
+
+    fn on_timeout(&mut self) {
+        self.cwnd = self.mtu;
+    }
+
+    fn on_loss(&mut self, _flight_size: u32) {
+        // losses aren't treated as a congestion signal; the model already
+        // bounds how much data is in flight
+    }
+
+    fn set_mtu(&mut self, mtu: u32) {
+        self.mtu = mtu;
+    }
+}
@@ -0,0 +1,65 @@
+use crate::chunk::{Cwr, Ecne};
+use crate::tsn::tsn_lte;
+
+/// Reacts to the ECNEs of the peer (RFC 4960 appendix A).
+/// The cwnd is reduced at most once per window of data: ECNEs for DATA sent
+/// before the last reduction only get a CWR in response.
+#[derive(Default)]
+pub struct EcnSender {
+    // highest TSN sent when the cwnd was last reduced
+    recovery_tsn: Option<u32>,
+}
+
+impl EcnSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Processes an ECNE given the highest TSN sent so far.
+    /// Returns the CWR to send back and whether the cwnd must be reduced.
+    pub fn on_ecne(&mut self, ecne: &Ecne, highest_tsn: u32) -> (Cwr, bool) {
+        let reduce = !self
+            .recovery_tsn
+            .is_some_and(|recovery_tsn| tsn_lte(ecne.lowest_tsn(), recovery_tsn));
+        if reduce {
+            self.recovery_tsn = Some(highest_tsn);
+        }
+        (Cwr::new(self.recovery_tsn.unwrap_or(highest_tsn)), reduce)
+    }
+}
+
+/// Keeps echoing the CE marks of recvd DATA to the peer until it
+/// says it reduced its cwnd (RFC 4960 appendix A)
+#[derive(Default)]
+pub struct EcnReceiver {
+    // TSN of the last DATA recvd with the CE codepoint set
+    ce_tsn: Option<u32>,
+}
+
+impl EcnReceiver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a DATA chunk that arrived in a datagram with the CE codepoint set
+    pub fn on_ce(&mut self, tsn: u32) {
+        if !self.ce_tsn.is_some_and(|ce_tsn| tsn_lte(tsn, ce_tsn)) {
+            self.ce_tsn = Some(tsn);
+        }
+    }
+
+    /// Stops echoing once the peer reduced its cwnd for all the CE marks seen so far
+    pub fn on_cwr(&mut self, cwr: &Cwr) {
+        if self
+            .ce_tsn
+            .is_some_and(|ce_tsn| tsn_lte(ce_tsn, cwr.lowest_tsn()))
+        {
+            self.ce_tsn = None;
+        }
+    }
+
+    /// Returns the ECNE to bundle with every SACK until a CWR arrives
+    pub fn ecne(&self) -> Option<Ecne> {
+        self.ce_tsn.map(Ecne::new)
+    }
+}
@@ -1,15 +1,19 @@
 use crate::association::Association;
 use crate::chunk::Init;
+use crate::config::{AssociationConfig, SendOptions};
 use crate::error::SCTPError;
+use crate::notification::Notification;
+use crate::pr::AbandonedStats;
+use crate::reassembly::Message;
+use crate::rto::SrttReport;
 use log::{debug, error, info};
 use std::net::SocketAddr;
+use std::time::Duration;
 use tokio::net::UdpSocket;
 
 /// An SCTP endpoint.
 /// All methods inside this struct are meant to be called from the ULP.
 pub struct SCTPEndpoint {
-    local_addr: SocketAddr,
-    dst_addr: Option<SocketAddr>,
     association: Association,
 }
 
@@ -24,38 +28,132 @@ impl SCTPEndpoint {
         dst_addr: impl AsRef<str>,
         num_outbound_streams: u16,
     ) -> Result<Self, SCTPError> {
-        let local_address: SocketAddr = local_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidLocalAddress)?;
-        let dst_address: SocketAddr = dst_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidRemoteAddress)?;
+        let config = AssociationConfig {
+            num_outbound_streams,
+            ..AssociationConfig::default()
+        };
+        Self::associate_send_with_config(local_addr, dst_addr, config).await
+    }
 
-        let association = Association::new_sender(local_addr, dst_addr).await?;
+    /// Create an association from sender side using the given config
+    pub async fn associate_send_with_config(
+        local_addr: impl AsRef<str>,
+        dst_addr: impl AsRef<str>,
+        config: AssociationConfig,
+    ) -> Result<Self, SCTPError> {
+        Self::associate_multihomed_send(&[local_addr], &[dst_addr], config).await
+    }
 
-        Ok(Self {
-            local_addr: local_address,
-            dst_addr: Some(dst_address),
-            association,
-        })
+    /// Create an association from sender side bound to all the given local addrs.
+    /// The first destination addr is the primary one.
+    pub async fn associate_multihomed_send(
+        local_addrs: &[impl AsRef<str>],
+        dst_addrs: &[impl AsRef<str>],
+        config: AssociationConfig,
+    ) -> Result<Self, SCTPError> {
+        let local_addrs = parse_addrs(local_addrs).map_err(|_| SCTPError::InvalidLocalAddress)?;
+        let dst_addrs = parse_addrs(dst_addrs).map_err(|_| SCTPError::InvalidRemoteAddress)?;
+
+        let association = Association::new_sender(local_addrs, dst_addrs, config).await?;
+
+        Ok(Self { association })
     }
 
     /// Create an association from receiver side
     pub async fn associate_recv(local_addr: impl AsRef<str>) -> Result<Self, SCTPError> {
-        let local_address: SocketAddr = local_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidLocalAddress)?;
+        Self::associate_recv_with_config(local_addr, AssociationConfig::default()).await
+    }
 
-        let association = Association::new_recvr(local_addr).await?;
+    /// Create an association from receiver side using the given config
+    pub async fn associate_recv_with_config(
+        local_addr: impl AsRef<str>,
+        config: AssociationConfig,
+    ) -> Result<Self, SCTPError> {
+        Self::associate_multihomed_recv(&[local_addr], config).await
+    }
+
+    /// Create an association from receiver side bound to all the given local addrs
+    pub async fn associate_multihomed_recv(
+        local_addrs: &[impl AsRef<str>],
+        config: AssociationConfig,
+    ) -> Result<Self, SCTPError> {
+        let local_addrs = parse_addrs(local_addrs).map_err(|_| SCTPError::InvalidLocalAddress)?;
 
-        Ok(Self {
-            local_addr: local_address,
-            dst_addr: None,
-            association,
-        })
+        let association = Association::new_recvr(local_addrs, config).await?;
+
+        Ok(Self { association })
+    }
+
+    /// Returns the local addrs of the association
+    pub fn local_addrs(&self) -> &[SocketAddr] {
+        self.association.local_addrs()
+    }
+
+    /// Returns the addrs of the associated endpoint
+    pub fn dst_addrs(&self) -> Vec<SocketAddr> {
+        self.association.peer_addrs()
+    }
+
+    /// Adds a local addr to the association.
+    /// Fails with `AsconfNotSupported` unless the associated endpoint
+    /// supports ASCONF and AUTH has been negotiated.
+    pub async fn add_local_addr(&mut self, local_addr: impl AsRef<str>) -> Result<(), SCTPError> {
+        let local_addr = parse_addr(local_addr).map_err(|_| SCTPError::InvalidLocalAddress)?;
+        self.association.add_local_addr(local_addr).await
+    }
+
+    /// Removes a local addr from the association
+    pub async fn delete_local_addr(
+        &mut self,
+        local_addr: impl AsRef<str>,
+    ) -> Result<(), SCTPError> {
+        let local_addr = parse_addr(local_addr).map_err(|_| SCTPError::InvalidLocalAddress)?;
+        self.association.delete_local_addr(local_addr).await
+    }
+
+    /// Asks the associated endpoint to send its data to the given local addr
+    pub async fn set_peer_primary(&mut self, local_addr: impl AsRef<str>) -> Result<(), SCTPError> {
+        let local_addr = parse_addr(local_addr).map_err(|_| SCTPError::InvalidLocalAddress)?;
+        self.association.set_peer_primary(local_addr).await
+    }
+
+    /// Resets the seq nos of the given outbound streams, all of them if none are given.
+    /// Fails with `ReconfigNotSupported` unless the associated endpoint supports RE-CONFIG.
+    pub async fn reset_streams(&mut self, stream_ids: &[u16]) -> Result<(), SCTPError> {
+        self.association.reset_streams(stream_ids).await
+    }
+
+    /// Asks the associated endpoint to reset the seq nos of the given inbound streams,
+    /// all of them if none are given
+    pub async fn reset_incoming_streams(&mut self, stream_ids: &[u16]) -> Result<(), SCTPError> {
+        self.association.reset_incoming_streams(stream_ids).await
+    }
+
+    /// Resets the seq nos of all the streams and the TSNs in both directions
+    pub async fn reset_association(&mut self) -> Result<(), SCTPError> {
+        self.association.reset_association().await
+    }
+
+    /// Adds outbound streams and asks the associated endpoint to add inbound streams
+    pub async fn add_streams(
+        &mut self,
+        num_outbound: u16,
+        num_inbound: u16,
+    ) -> Result<(), SCTPError> {
+        self.association
+            .add_streams(num_outbound, num_inbound)
+            .await
+    }
+
+    /// Sets the priority of an outbound stream, used by the priority scheduler.
+    /// The lower the value, the sooner its msgs are sent.
+    pub fn set_stream_priority(&mut self, stream_id: u16, priority: u16) -> Result<(), SCTPError> {
+        self.association.set_stream_priority(stream_id, priority)
+    }
+
+    /// Sets the weight of an outbound stream, used by the weighted fair queueing scheduler
+    pub fn set_stream_weight(&mut self, stream_id: u16, weight: u16) -> Result<(), SCTPError> {
+        self.association.set_stream_weight(stream_id, weight)
     }
 
     /// Shutdown an association
@@ -68,28 +166,105 @@ impl SCTPEndpoint {
         let _ = self.association.abort().await;
     }
 
-    /// Send data to the associated endpoint
-    pub async fn send(&self, bytes: &[u8]) {}
+    /// Send data to the associated endpoint on stream 0
+    pub async fn send(&mut self, bytes: &[u8]) -> Result<(), SCTPError> {
+        self.send_with_options(bytes, SendOptions::default()).await
+    }
 
-    pub fn set_primary(&self) {
-        // Not needed for WebRTC
+    /// Send data to the associated endpoint using the given options
+    pub async fn send_with_options(
+        &mut self,
+        bytes: &[u8],
+        options: SendOptions,
+    ) -> Result<(), SCTPError> {
+        self.association.send(bytes, options).await
     }
 
-    pub async fn receive(&self) -> Vec<u8> {
-        // TODO: abhi - pop message from association's queue
-        // let packet = self.association.msg_queue.pop_front();
-        todo!()
+    /// Waits until all the data sent so far has been acked by the associated endpoint
+    /// or abandoned. Only needed when the send buffer lets sends return early.
+    pub async fn flush(&mut self) -> Result<(), SCTPError> {
+        self.association.flush().await
+    }
+
+    /// Returns the number of msgs and bytes abandoned under each PR policy,
+    /// split into the ones that were sent and the ones that weren't
+    pub fn abandoned_stats(&self) -> AbandonedStats {
+        self.association.abandoned_stats()
+    }
+
+    /// Returns true if I-DATA has been negotiated and msgs on different streams
+    /// are interleaved
+    pub fn interleaving(&self) -> bool {
+        self.association.interleaving()
+    }
+
+    /// Sets the addr of the associated endpoint new data is sent to.
+    /// The addr must be one of the addrs of the associated endpoint.
+    pub fn set_primary(&mut self, dst_addr: impl AsRef<str>) -> Result<(), SCTPError> {
+        let dst_addr = parse_addr(dst_addr).map_err(|_| SCTPError::InvalidRemoteAddress)?;
+        self.association.set_primary(dst_addr)
+    }
+
+    /// Returns the addr of the associated endpoint new data is sent to
+    pub fn primary_addr(&self) -> SocketAddr {
+        self.association.primary()
+    }
+
+    /// Adds or replaces a shared key used to authenticate chunks with the associated endpoint.
+    /// The key with id 0 is the empty key unless it is replaced.
+    pub fn add_shared_key(&mut self, key_id: u16, key: &[u8]) {
+        self.association.add_shared_key(key_id, key.to_vec())
+    }
+
+    /// Authenticates the chunks sent from now on with the given shared key
+    pub fn set_active_key(&mut self, key_id: u16) -> Result<(), SCTPError> {
+        self.association.set_active_key(key_id)
+    }
+
+    /// Deletes a shared key. The active key can't be deleted.
+    pub fn delete_shared_key(&mut self, key_id: u16) -> Result<(), SCTPError> {
+        self.association.delete_shared_key(key_id)
+    }
+
+    /// Receive the next msg sent by the associated endpoint
+    pub async fn receive(&mut self) -> Result<Vec<u8>, SCTPError> {
+        Ok(self.receive_message().await?.data)
+    }
+
+    /// Receive the next msg sent by the associated endpoint
+    /// along with the stream and ppid it was sent with
+    pub async fn receive_message(&mut self) -> Result<Message, SCTPError> {
+        self.association.recv().await
     }
 
     pub fn status(&self) {}
 
-    pub fn change_heartbeat(&self) {}
+    /// Enables or disables heartbeats and changes the interval between them
+    pub fn change_heartbeat(&mut self, enabled: bool, interval: Duration) {
+        self.association.change_heartbeat(enabled, interval);
+    }
 
-    pub fn request_heartbeat(&self) {}
+    /// Sends a heartbeat to the associated endpoint right away
+    pub async fn request_heartbeat(&mut self) -> Result<(), SCTPError> {
+        self.association.request_heartbeat().await
+    }
 
-    pub fn get_srtt_report(&self) {}
+    /// Returns the current SRTT, RTTVAR and RTO of the association
+    pub fn get_srtt_report(&self) -> SrttReport {
+        self.association.srtt_report()
+    }
 
-    pub fn set_failure_threshold(&self) {}
+    /// Sets the number of consecutive errors after which the association fails
+    /// and after which a destination address is marked inactive
+    pub fn set_failure_threshold(&mut self, association_max_retrans: u32, path_max_retrans: u32) {
+        self.association
+            .set_failure_threshold(association_max_retrans, path_max_retrans);
+    }
+
+    /// Returns the next notification about the association, if any
+    pub fn poll_notification(&mut self) -> Option<Notification> {
+        self.association.poll_notification()
+    }
 
     pub fn set_protocol_params(&self) {}
 
@@ -99,3 +274,15 @@ impl SCTPEndpoint {
 
     pub fn destroy(&self) {}
 }
+
+/// Parses every addr in the list. Fails if the list is empty or an addr is invalid.
+fn parse_addrs(addrs: &[impl AsRef<str>]) -> Result<Vec<SocketAddr>, ()> {
+    if addrs.is_empty() {
+        return Err(());
+    }
+    addrs.iter().map(parse_addr).collect()
+}
+
+fn parse_addr(addr: impl AsRef<str>) -> Result<SocketAddr, ()> {
+    addr.as_ref().parse().map_err(|_| ())
+}
@@ -30,4 +30,22 @@ pub enum SCTPError {
     InvalidSCTPPacket,
     #[error("rtx timeout")]
     RetransmissionTimeout,
+    #[error("msg is larger than the max msg size")]
+    MessageTooLarge,
+    #[error("stream id is not valid for this association")]
+    InvalidStreamId,
+    #[error("association failed as the peer is unreachable")]
+    AssociationFailed,
+    #[error("dynamic address reconfiguration requires AUTH to be negotiated with the peer")]
+    AsconfNotSupported,
+    #[error("peer refused the address reconfiguration")]
+    AsconfRefused,
+    #[error("no shared key with this key id or the key is in use")]
+    InvalidKeyId,
+    #[error("peer doesn't support stream reconfiguration")]
+    ReconfigNotSupported,
+    #[error("peer refused the stream reconfiguration")]
+    ReconfigRefused,
+    #[error("ECN codepoints can't be set on the socket")]
+    EcnUnavailable,
 }
@@ -0,0 +1,119 @@
+use std::convert::TryFrom;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Default interval between heartbeats on an idle path
+pub const HB_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Sender-specific info carried in the heartbeat info TLV of a HEARTBEAT.
+/// The peer echoes it back as is in the HEARTBEAT ACK.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeartbeatInfo {
+    /// microseconds since the heartbeat timer was created
+    pub timestamp: u64,
+    /// random value that must match the one of the heartbeat being acked
+    pub nonce: u64,
+}
+
+impl From<&HeartbeatInfo> for Vec<u8> {
+    fn from(info: &HeartbeatInfo) -> Self {
+        let mut v = Vec::with_capacity(16);
+        v.extend(info.timestamp.to_be_bytes());
+        v.extend(info.nonce.to_be_bytes());
+        v
+    }
+}
+
+impl TryFrom<&[u8]> for HeartbeatInfo {
+    type Error = ();
+
+    fn try_from(buf: &[u8]) -> Result<Self, Self::Error> {
+        if buf.len() != 16 {
+            return Err(());
+        }
+        Ok(Self {
+            timestamp: u64::from_be_bytes(<[u8; 8]>::try_from(&buf[0..8]).unwrap()),
+            nonce: u64::from_be_bytes(<[u8; 8]>::try_from(&buf[8..16]).unwrap()),
+        })
+    }
+}
+
+/// Decides when heartbeats are sent to a path and
+/// matches the HEARTBEAT ACKs that come back (section 8.3)
+pub struct HeartbeatTimer {
+    enabled: bool,
+    interval: Duration,
+    // reference point of the timestamps in the heartbeat info
+    epoch: Instant,
+    // when the next heartbeat is due
+    deadline: Instant,
+    // nonce of the heartbeat waiting to be acked
+    pending: Option<u64>,
+}
+
+impl HeartbeatTimer {
+    pub fn new(interval: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            enabled: true,
+            interval,
+            epoch: now,
+            deadline: now + interval,
+            pending: None,
+        }
+    }
+
+    /// Enables or disables heartbeats and changes the interval between them
+    pub fn set(&mut self, enabled: bool, interval: Duration) {
+        self.enabled = enabled;
+        self.interval = interval;
+    }
+
+    /// Returns when the next heartbeat is due, if heartbeats are enabled
+    pub fn deadline(&self) -> Option<Instant> {
+        if self.enabled {
+            Some(self.deadline)
+        } else {
+            None
+        }
+    }
+
+    /// Restarts the timer. The next heartbeat is due after
+    /// RTO + HB.interval, with `jitter` (within +/- 50% of the RTO) added to it
+    pub fn restart(&mut self, rto: Duration, jitter: f64) {
+        let jitter = jitter.clamp(-0.5, 0.5);
+        let delay = (rto + self.interval).as_secs_f64() + rto.as_secs_f64() * jitter;
+        self.deadline = Instant::now() + Duration::from_secs_f64(delay);
+    }
+
+    /// Restarts the timer so the next heartbeat is due after `delay`
+    pub fn restart_after(&mut self, delay: Duration) {
+        self.deadline = Instant::now() + delay;
+    }
+
+    /// Returns true if the heartbeat sent before hasn't been acked yet
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns the info to send in a new HEARTBEAT.
+    /// Any earlier heartbeat that is still pending is forgotten.
+    pub fn on_send(&mut self, nonce: u64) -> HeartbeatInfo {
+        self.pending = Some(nonce);
+        HeartbeatInfo {
+            timestamp: self.epoch.elapsed().as_micros() as u64,
+            nonce,
+        }
+    }
+
+    /// Matches the info echoed back in a HEARTBEAT ACK against the pending heartbeat
+    /// and returns the RTT measured with it
+    pub fn on_ack(&mut self, info: &HeartbeatInfo) -> Option<Duration> {
+        if self.pending != Some(info.nonce) {
+            return None;
+        }
+        self.pending = None;
+        let sent_at = Duration::from_micros(info.timestamp);
+        self.epoch.elapsed().checked_sub(sent_at)
+    }
+}
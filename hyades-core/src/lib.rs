@@ -1,9 +1,26 @@
+pub mod asconf;
 pub mod association;
+pub mod auth;
 pub mod chunk;
+pub mod config;
+pub mod congestion;
 pub mod cookie;
+pub mod ecn;
 pub mod endpoint;
 pub mod error;
+pub mod heartbeat;
+pub mod notification;
 pub mod packet;
+pub mod path;
+pub mod pmtud;
+pub mod pr;
+pub mod reassembly;
+pub mod reconfig;
+pub mod recv_buffer;
+pub mod reorder;
+pub mod rto;
+pub mod scheduler;
 pub mod stream;
+pub mod tsn;
 
 pub use endpoint::SCTPEndpoint;
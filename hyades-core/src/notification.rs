@@ -0,0 +1,30 @@
+use crate::path::PathState;
+use std::net::SocketAddr;
+
+/// Events the ULP is told about (section 10.2)
+#[derive(Clone, Debug, PartialEq)]
+pub enum Notification {
+    /// a destination address of the peer changed its reachability
+    NetworkStatusChange { addr: SocketAddr, state: PathState },
+    /// the peer added an address to the association
+    PeerAddrAdded { addr: SocketAddr },
+    /// the peer removed an address from the association
+    PeerAddrRemoved { addr: SocketAddr },
+    /// data is now sent to a different destination address of the peer
+    PrimaryChanged { addr: SocketAddr },
+    /// the seq nos of the given streams were reset, all of them if none are given.
+    /// `incoming` tells if the streams are the ones the peer sends on
+    StreamsReset {
+        stream_ids: Vec<u16>,
+        incoming: bool,
+    },
+    /// the seq nos of all the streams and the TSNs in both directions were reset
+    AssociationReset,
+    /// streams were added to the association
+    StreamsChanged { num_inbound: u16, num_outbound: u16 },
+    /// the peer announced its adaptation layer with the given code point
+    /// when the association was set up (RFC 5061 section 4.2.6)
+    AdaptationIndication { code_point: u32 },
+    /// the association has failed as the peer has become unreachable
+    CommunicationLost,
+}
@@ -1,8 +1,16 @@
-use crate::chunk::{Chunk, CookieAck, CookieEcho, Data, Init, InitAck};
+use crate::chunk::{
+    padding, Asconf, AsconfAck, Auth, Chunk, ChunkHeader, ChunkType, CookieAck, CookieEcho, Cwr,
+    Data, Ecne, Error, ForwardTsn, Heartbeat, HeartbeatAck, IData, IForwardTsn, Init, InitAck,
+    NrSack, Pad, ReConfig, Sack,
+};
 use crate::error::SCTPError;
 use crc32c;
 use std::convert::TryFrom;
 
+// section 3.2: high bits of a chunk type saying what to do with a chunk of an unknown type
+const SKIP_UNRECOGNIZED: u8 = 0x80;
+const REPORT_UNRECOGNIZED: u8 = 0x40;
+
 /*
         0                   1                   2                   3
         0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1 2 3 4 5 6 7 8 9 0 1
@@ -51,96 +59,244 @@ impl Default for CommonHeader {
 pub struct Packet {
     pub header: CommonHeader,
     pub chunks: Vec<Box<dyn Chunk>>,
+    /// recvd chunks of an unknown type the peer wants to be told about
+    pub unrecognized: Vec<Vec<u8>>,
+    // length the packet is padded to with a PAD chunk when it is sent
+    min_len: usize,
+    // true if the packet is sent with a zero checksum
+    zero_checksum: bool,
+    // the bytes the packet was parsed from
+    raw: Vec<u8>,
+    // offset in `raw` where each chunk ends, including its padding
+    chunk_ends: Vec<usize>,
 }
 
 impl From<&Packet> for Vec<u8> {
     fn from(p: &Packet) -> Self {
-        let mut v = Vec::with_capacity(p.chunks.len());
-        for chunk in &p.chunks {
-            v.extend(Vec::<u8>::from(chunk));
-        }
-        v
+        let mut chunks = encode_chunks(&p.chunks);
+        chunks.extend(p.pad_chunk(chunks.len()));
+        p.encode(chunks)
+    }
+}
+
+/// Returns the wire format of the given chunks, each one padded to 4 bytes
+fn encode_chunks(chunks: &[Box<dyn Chunk>]) -> Vec<u8> {
+    let mut v = vec![];
+    for chunk in chunks {
+        let bytes = Vec::<u8>::from(chunk);
+        let len = bytes.len();
+        v.extend(bytes);
+        v.extend(std::iter::repeat_n(0, padding(len)));
     }
+    v
 }
 
 impl TryFrom<Vec<u8>> for Packet {
     type Error = SCTPError;
 
     fn try_from(raw_data: Vec<u8>) -> Result<Self, Self::Error> {
-        // TODO abhi: convert raw bytes into a packet
+        Packet::parse(raw_data, false)
+    }
+}
+
+impl Packet {
+    /// Parses a recvd packet. A packet with a zero checksum is only accepted
+    /// if `zero_checksum_acceptable`, a packet with a correct CRC32c always is
+    /// (RFC 9653 section 5.3).
+    pub fn parse(mut raw_data: Vec<u8>, zero_checksum_acceptable: bool) -> Result<Self, SCTPError> {
+        if raw_data.len() < 12 {
+            return Err(SCTPError::InvalidSCTPPacket);
+        }
+
         let header = CommonHeader {
             src_port: u16::from_be_bytes(<[u8; 2]>::try_from(&raw_data[0..2]).unwrap()),
             dst_port: u16::from_be_bytes(<[u8; 2]>::try_from(&raw_data[2..4]).unwrap()),
             ver_tag: u32::from_be_bytes(<[u8; 4]>::try_from(&raw_data[4..8]).unwrap()),
-            checksum: u32::from_be_bytes(<[u8; 4]>::try_from(&raw_data[8..12]).unwrap()),
+            checksum: u32::from_le_bytes(<[u8; 4]>::try_from(&raw_data[8..12]).unwrap()),
         };
 
+        raw_data[8..12].copy_from_slice(&[0u8; 4]);
+        let zero_checksum = zero_checksum_acceptable && header.checksum == 0;
+        if !zero_checksum && crc32c::crc32c(&raw_data) != header.checksum {
+            return Err(SCTPError::InvalidSCTPPacket);
+        }
+
         let mut offset = 12usize;
         let mut chunks: Vec<Box<dyn Chunk>> = vec![];
+        let mut unrecognized = vec![];
+        let mut chunk_ends = vec![];
+
+        while offset + 4 <= raw_data.len() {
+            let len = ChunkHeader::parse(&raw_data[offset..]).length() as usize;
+            if len < 4 || offset + len > raw_data.len() {
+                return Err(SCTPError::InvalidSCTPPacket);
+            }
+            let buf = &raw_data[offset..offset + len];
+            let next = (offset + len + padding(len)).min(raw_data.len());
 
-        while offset < raw_data.len() {
             match raw_data[offset] {
-                0 => {
-                    let len = u16::from_be_bytes(
-                        <[u8; 2]>::try_from(&raw_data[offset + 3..=offset + 4]).unwrap(),
-                    ) as usize;
-                    chunks.push(Box::new(Data::from(&raw_data[offset..len])));
-                    offset += len;
-                }
-                1 => {
-                    let len = u16::from_be_bytes(
-                        <[u8; 2]>::try_from(&raw_data[offset + 3..=offset + 4]).unwrap(),
-                    ) as usize;
-                    chunks.push(Box::new(Init::from(&raw_data[offset..len])));
-                    offset += len;
+                0 if len >= 17 => chunks.push(Box::new(Data::from(buf))),
+                1 if len >= 20 => chunks.push(Box::new(Init::from(buf))),
+                2 if len >= 20 => chunks.push(Box::new(InitAck::from(buf))),
+                3 if len >= 16 => chunks.push(Box::new(Sack::from(buf))),
+                4 => chunks.push(Box::new(Heartbeat::from(buf))),
+                5 => chunks.push(Box::new(HeartbeatAck::from(buf))),
+                9 => chunks.push(Box::new(Error::from(buf))),
+                10 => chunks.push(Box::new(CookieEcho::from(buf))),
+                11 => chunks.push(Box::new(CookieAck::from(buf))),
+                12 if len >= 8 => chunks.push(Box::new(Ecne::from(buf))),
+                13 if len >= 8 => chunks.push(Box::new(Cwr::from(buf))),
+                15 if len >= 8 => chunks.push(Box::new(Auth::from(buf))),
+                0x10 if len >= 20 => chunks.push(Box::new(NrSack::from(buf))),
+                0x40 if len >= 20 => chunks.push(Box::new(IData::from(buf))),
+                0x80 if len >= 8 => chunks.push(Box::new(AsconfAck::from(buf))),
+                0x82 => chunks.push(Box::new(ReConfig::from(buf))),
+                0x84 => chunks.push(Box::new(Pad::from(buf))),
+                0xc0 if len >= 8 => chunks.push(Box::new(ForwardTsn::from(buf))),
+                0xc1 if len >= 8 => chunks.push(Box::new(Asconf::from(buf))),
+                0xc2 if len >= 8 => chunks.push(Box::new(IForwardTsn::from(buf))),
+                // a known chunk too short for its fixed fields
+                0..=3 | 12 | 13 | 15 | 0x10 | 0x40 | 0x80 | 0xc0 | 0xc1 | 0xc2 => {
+                    return Err(SCTPError::InvalidSCTPPacket)
                 }
-                2 => {
-                    let len = u16::from_be_bytes(
-                        <[u8; 2]>::try_from(&raw_data[offset + 3..=offset + 4]).unwrap(),
-                    ) as usize;
-                    chunks.push(Box::new(InitAck::from(&raw_data[offset..len])));
-                    offset += len;
+                // section 3.2: the 2 high bits of an unknown chunk type say whether
+                // the rest of the packet is processed and whether the peer is told
+                chunk_type => {
+                    if chunk_type & REPORT_UNRECOGNIZED != 0 {
+                        unrecognized.push(buf.to_vec());
+                    }
+                    if chunk_type & SKIP_UNRECOGNIZED == 0 {
+                        // the chunks after it are discarded, the ones before it are kept
+                        break;
+                    }
+                    offset = next;
+                    continue;
                 }
-                10 => {
-                    let len = u16::from_be_bytes(
-                        <[u8; 2]>::try_from(&raw_data[offset + 3..=offset + 4]).unwrap(),
-                    ) as usize;
-                    chunks.push(Box::new(CookieEcho::from(&raw_data[offset..len])));
-                    offset += len;
-                }
-                11 => {
-                    let len = u16::from_be_bytes(
-                        <[u8; 2]>::try_from(&raw_data[offset + 3..=offset + 4]).unwrap(),
-                    ) as usize;
-                    chunks.push(Box::new(CookieAck::from(&raw_data[offset..len])));
-                    offset += len;
-                }
-                _ => return Err(SCTPError::InvalidSCTPPacket),
             }
+
+            offset = next;
+            chunk_ends.push(offset);
         }
 
-        Ok(Packet { header, chunks })
+        Ok(Packet {
+            header,
+            chunks,
+            unrecognized,
+            min_len: 0,
+            zero_checksum: false,
+            raw: raw_data,
+            chunk_ends,
+        })
     }
-}
 
-impl Packet {
     /// Creates a new `Packet`
-    pub fn new(src_port: u16, dst_port: u16) -> Self {
+    pub fn new(src_port: u16, dst_port: u16, ver_tag: u32) -> Self {
         let mut header = CommonHeader::default();
         header.src_port = src_port;
         header.dst_port = dst_port;
-        // TODO abhi: init the verification tag
-        let mut packet = Self {
+        header.ver_tag = ver_tag;
+        Self {
             header,
             chunks: Vec::new(),
-        };
-        let checksum = crc32c::crc32c(&Vec::<u8>::from(&packet));
-        packet.header.checksum = checksum;
-        packet
+            unrecognized: Vec::new(),
+            min_len: 0,
+            zero_checksum: false,
+            raw: Vec::new(),
+            chunk_ends: Vec::new(),
+        }
+    }
+
+    /// Pads the packet with a PAD chunk when it is sent so that it is at least
+    /// `len` bytes long, common header included (RFC 4820).
+    /// A packet with an INIT, INIT ACK or SHUTDOWN COMPLETE isn't padded
+    /// as they can't be bundled with other chunks (section 6.10).
+    pub fn pad_to(&mut self, len: usize) {
+        self.min_len = len;
+    }
+
+    /// Sends the packet with a zero checksum, once the peer has said it accepts them.
+    /// A packet with an INIT, INIT ACK or COOKIE ECHO still carries the CRC32c
+    /// as the peer may not have learned that we send zero checksums (RFC 9653 section 5.2).
+    pub fn set_zero_checksum(&mut self, zero_checksum: bool) {
+        self.zero_checksum = zero_checksum;
+    }
+
+    /// Returns the PAD chunk to add to `len` bytes of chunks, if any
+    fn pad_chunk(&self, len: usize) -> Vec<u8> {
+        let missing = self.min_len.saturating_sub(12 + len);
+        let unbundled = self.chunks.iter().any(|chunk| {
+            matches!(
+                chunk.chunk_type(),
+                ChunkType::Init | ChunkType::InitAck | ChunkType::ShutdownComplete
+            )
+        });
+        if missing == 0 || unbundled {
+            return vec![];
+        }
+        // the PAD chunk is at least a header long and keeps the packet 4 byte aligned
+        let pad_len = (missing + padding(missing)).max(4);
+        Pad::new(pad_len - 4).get_bytes()
+    }
+
+    /// Returns the wire format of the packet with the given chunks
+    fn encode(&self, chunks: Vec<u8>) -> Vec<u8> {
+        let mut v = Vec::with_capacity(12 + chunks.len());
+        v.extend(self.header.src_port.to_be_bytes());
+        v.extend(self.header.dst_port.to_be_bytes());
+        v.extend(self.header.ver_tag.to_be_bytes());
+        // the checksum is calculated with the checksum field set to 0
+        v.extend([0u8; 4]);
+        v.extend(chunks);
+        let needs_crc = self.chunks.iter().any(|chunk| {
+            matches!(
+                chunk.chunk_type(),
+                ChunkType::Init | ChunkType::InitAck | ChunkType::CookieEcho
+            )
+        });
+        if self.zero_checksum && !needs_crc {
+            return v;
+        }
+        let checksum = crc32c::crc32c(&v);
+        v[8..12].copy_from_slice(&checksum.to_le_bytes());
+        v
+    }
+
+    /// Returns the wire format of the packet with an AUTH chunk in front of the chunk at `idx`.
+    /// `sign` creates the AUTH chunk from the bytes of the chunks it covers (RFC 4895 section 6.2).
+    /// The PAD chunk the packet is padded with is covered too.
+    pub fn to_authenticated_bytes(&self, idx: usize, sign: impl Fn(&[u8]) -> Auth) -> Vec<u8> {
+        let mut chunks = encode_chunks(&self.chunks[..idx]);
+        let mut covered = encode_chunks(&self.chunks[idx..]);
+        let mut auth = sign(&covered).get_bytes();
+        let pad = self.pad_chunk(chunks.len() + auth.len() + padding(auth.len()) + covered.len());
+        if !pad.is_empty() {
+            covered.extend(pad);
+            auth = sign(&covered).get_bytes();
+        }
+        let len = auth.len();
+        chunks.extend(auth);
+        chunks.extend(std::iter::repeat_n(0, padding(len)));
+        chunks.extend(covered);
+        self.encode(chunks)
+    }
+
+    /// Returns the bytes of every chunk after the chunk at `idx` as they were recvd
+    pub fn bytes_after(&self, idx: usize) -> &[u8] {
+        self.chunk_ends
+            .get(idx)
+            .map_or(&[], |&end| &self.raw[end..])
     }
 
     /// Add a chunk to this packet
     pub fn add_chunk(&mut self, chunk: Box<dyn Chunk>) {
         self.chunks.push(chunk);
     }
+
+    /// Returns the first chunk of the given type in this packet
+    pub fn find_chunk(&self, chunk_type: ChunkType) -> Option<&dyn Chunk> {
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.chunk_type() == chunk_type)
+            .map(|chunk| chunk.as_ref())
+    }
 }
@@ -0,0 +1,140 @@
+use crate::config::AssociationConfig;
+use crate::congestion::CongestionControl;
+use crate::heartbeat::HeartbeatTimer;
+use crate::pmtud::{base_plpmtu, Plpmtud};
+use crate::rto::Rto;
+use std::net::SocketAddr;
+
+/// Reachability of a destination transport address
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathState {
+    /// the path is used for sending data
+    Active,
+    /// the path has seen more errors than the PF threshold allows (RFC 7829).
+    /// it is probed with heartbeats every RTO until it recovers or fails
+    PotentiallyFailed,
+    /// the path has seen more errors than Path.Max.Retrans allows (section 8.2)
+    Inactive,
+}
+
+/// Error thresholds of a path
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PathThresholds {
+    /// errors after which the path is potentially failed
+    pub pf_max_retrans: u32,
+    /// errors after which the path is inactive
+    pub max_retrans: u32,
+}
+
+impl From<&AssociationConfig> for PathThresholds {
+    fn from(config: &AssociationConfig) -> Self {
+        Self {
+            pf_max_retrans: config.path_pf_max_retrans,
+            max_retrans: config.path_max_retrans,
+        }
+    }
+}
+
+/// A destination transport address of the peer along with
+/// the state kept for it (section 6.4)
+pub struct Path {
+    addr: SocketAddr,
+    state: PathState,
+    // consecutive T3-rtx expirations and unacked heartbeats
+    error_count: u32,
+    thresholds: PathThresholds,
+    pub(crate) cc: Box<dyn CongestionControl>,
+    pub(crate) rto: Rto,
+    pub(crate) heartbeat: HeartbeatTimer,
+    pub(crate) plpmtud: Plpmtud,
+}
+
+impl Path {
+    pub fn new(addr: SocketAddr, config: &AssociationConfig) -> Self {
+        let plpmtud = Plpmtud::new(
+            base_plpmtu(&addr),
+            config.max_plpmtu,
+            config.plpmtud_enabled,
+        );
+        Self {
+            addr,
+            state: PathState::Active,
+            error_count: 0,
+            thresholds: config.into(),
+            cc: config.congestion_algorithm.build(plpmtud.plpmtu()),
+            rto: Rto::new(config.rto_initial, config.rto_min, config.rto_max),
+            heartbeat: HeartbeatTimer::new(config.heartbeat_interval),
+            plpmtud,
+        }
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    pub fn state(&self) -> PathState {
+        self.state
+    }
+
+    /// Returns the largest packet size known to get through to the addr
+    pub fn pmtu(&self) -> u32 {
+        self.plpmtud.plpmtu()
+    }
+
+    pub fn error_count(&self) -> u32 {
+        self.error_count
+    }
+
+    pub fn set_thresholds(&mut self, thresholds: PathThresholds) {
+        self.thresholds = thresholds;
+    }
+
+    /// Counts a T3-rtx expiration or an unacked heartbeat.
+    /// Returns the new state of the path if it changed.
+    pub fn on_error(&mut self) -> Option<PathState> {
+        self.error_count += 1;
+
+        let state = if self.error_count > self.thresholds.max_retrans {
+            PathState::Inactive
+        } else if self.error_count > self.thresholds.pf_max_retrans {
+            PathState::PotentiallyFailed
+        } else {
+            self.state
+        };
+        // an inactive path only becomes active again once it is acked
+        if state == self.state || self.state == PathState::Inactive {
+            return None;
+        }
+        self.state = state;
+        Some(state)
+    }
+
+    /// Clears the error count after the peer acked something sent on this path.
+    /// Returns the new state of the path if it changed.
+    pub fn on_ack(&mut self) -> Option<PathState> {
+        self.error_count = 0;
+        if self.state == PathState::Active {
+            return None;
+        }
+        self.state = PathState::Active;
+        Some(PathState::Active)
+    }
+}
+
+/// Returns the index of the path new DATA should be sent on.
+/// `current` is the path used so far and `primary` the one set by the ULP.
+/// The primary is used whenever it is active if `failback` is set.
+/// Otherwise the current path is kept while it is active, else the first
+/// active path after it takes over (section 6.4.1). If no path is active,
+/// a potentially failed one is preferred over an inactive one (RFC 7829 section 5.1).
+pub fn select_primary(paths: &[Path], current: usize, primary: usize, failback: bool) -> usize {
+    if failback && paths[primary].state() == PathState::Active {
+        return primary;
+    }
+
+    let candidates = || (0..paths.len()).map(|offset| (current + offset) % paths.len());
+    candidates()
+        .find(|&path| paths[path].state() == PathState::Active)
+        .or_else(|| candidates().find(|&path| paths[path].state() == PathState::PotentiallyFailed))
+        .unwrap_or(current)
+}
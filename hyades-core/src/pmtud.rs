@@ -0,0 +1,236 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// PLPMTU assumed to work on every IPv4 path (RFC 8899 section 5.1.2)
+pub const BASE_PLPMTU_V4: u32 = 1200;
+/// PLPMTU assumed to work on every IPv6 path, the IPv6 minimum MTU
+pub const BASE_PLPMTU_V6: u32 = 1280;
+/// Default largest PLPMTU searched for
+pub const MAX_PLPMTU: u32 = 1500;
+/// Probes of a size that are lost before the size is considered too big
+pub const MAX_PROBES: u32 = 3;
+/// Time after which the search for a larger PLPMTU starts over
+pub const PMTU_RAISE_TIMER: Duration = Duration::from_secs(600);
+// the search stops once the size known to work is this close to the one known to fail
+const SEARCH_GRANULARITY: u32 = 16;
+
+/// States of the DPLPMTUD state machine (RFC 8899 section 5.2)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlpmtudState {
+    /// PLPMTUD is disabled and the PLPMTU is fixed
+    Disabled,
+    /// the base PLPMTU is being confirmed
+    Base,
+    /// probes of increasing size are sent to find a larger PLPMTU
+    Search,
+    /// the largest PLPMTU has been found. the search starts over
+    /// once the raise timer expires
+    SearchComplete,
+    /// not even probes of the base PLPMTU make it to the peer.
+    /// the base PLPMTU is kept and probed until a probe gets through
+    Error,
+}
+
+/// Returns the base PLPMTU of a path to the given addr
+pub fn base_plpmtu(addr: &SocketAddr) -> u32 {
+    if addr.is_ipv6() {
+        BASE_PLPMTU_V6
+    } else {
+        BASE_PLPMTU_V4
+    }
+}
+
+/// Datagram packetization layer PMTU discovery of a path (RFC 8899).
+/// Sizes are the sizes of the IP packets, headers included.
+/// Probes are HEARTBEATs padded to the probed size (RFC 8899 section 6.2.1.2)
+/// and their loss doesn't count as a path error or congestion.
+pub struct Plpmtud {
+    state: PlpmtudState,
+    base: u32,
+    max: u32,
+    // largest size confirmed to get through
+    plpmtu: u32,
+    // smallest size known not to get through
+    too_big: u32,
+    // size probed for
+    probe_size: u32,
+    // probes of `probe_size` sent so far
+    probe_count: u32,
+    // nonce of the probe waiting to be acked
+    pending: Option<u64>,
+    // consecutive losses of DATA larger than the base PLPMTU
+    black_hole_count: u32,
+    // when the state machine has to run next
+    timer: Option<Instant>,
+}
+
+impl Plpmtud {
+    /// Creates the state machine of a path. Probing starts right away if `enabled`,
+    /// otherwise `max` is used as the PLPMTU as is.
+    pub fn new(base: u32, max: u32, enabled: bool) -> Self {
+        // probes keep the 4 byte alignment of chunks
+        let max = (max & !3).max(base);
+        let (state, plpmtu, timer) = if enabled {
+            (PlpmtudState::Base, base, Some(Instant::now()))
+        } else {
+            (PlpmtudState::Disabled, max, None)
+        };
+        Self {
+            state,
+            base,
+            max,
+            plpmtu,
+            too_big: max + 1,
+            probe_size: base,
+            probe_count: 0,
+            pending: None,
+            black_hole_count: 0,
+            timer,
+        }
+    }
+
+    pub fn state(&self) -> PlpmtudState {
+        self.state
+    }
+
+    /// Returns the largest packet size confirmed to get through
+    pub fn plpmtu(&self) -> u32 {
+        self.plpmtu
+    }
+
+    /// Returns when `on_timer` has to be called next
+    pub fn deadline(&self) -> Option<Instant> {
+        self.timer
+    }
+
+    /// Runs the state machine once its deadline has passed. A probe that hasn't
+    /// been acked by now is lost. Returns the size of the probe to send next
+    /// with the given nonce, which is counted as lost if it isn't acked within `rto`.
+    pub fn on_timer(&mut self, now: Instant, rto: Duration, nonce: u64) -> Option<u32> {
+        if self.timer.is_none_or(|timer| timer > now) {
+            return None;
+        }
+
+        if self.pending.take().is_some() {
+            self.on_probe_lost(now);
+        } else if self.state == PlpmtudState::SearchComplete {
+            // the raise timer expired
+            self.start_search(now);
+        }
+
+        match self.state {
+            PlpmtudState::Base | PlpmtudState::Search | PlpmtudState::Error => {
+                self.pending = Some(nonce);
+                self.probe_count += 1;
+                self.timer = Some(now + rto);
+                Some(self.probe_size)
+            }
+            _ => None,
+        }
+    }
+
+    /// Matches the nonce echoed back in a HEARTBEAT ACK against the pending probe.
+    /// Returns true if the ack was for the probe.
+    pub fn on_ack(&mut self, nonce: u64, now: Instant) -> bool {
+        if self.pending != Some(nonce) {
+            return false;
+        }
+        self.pending = None;
+        self.plpmtu = self.probe_size;
+        self.black_hole_count = 0;
+        match self.state {
+            PlpmtudState::Base | PlpmtudState::Error => self.start_search(now),
+            _ => self.next_search_probe(now),
+        }
+        true
+    }
+
+    /// Records that DATA sent on the path in a packet of the given size was lost.
+    /// Repeated losses of packets larger than the base PLPMTU are taken as a black hole
+    /// and the PLPMTU falls back to the base one (RFC 8899 section 4.3).
+    /// Returns true if the PLPMTU dropped.
+    pub fn on_data_lost(&mut self, size: u32, now: Instant) -> bool {
+        let probing = matches!(
+            self.state,
+            PlpmtudState::Search | PlpmtudState::SearchComplete
+        );
+        if !probing || size <= self.base {
+            return false;
+        }
+        self.black_hole_count += 1;
+        if self.black_hole_count < MAX_PROBES {
+            return false;
+        }
+        self.pending = None;
+        self.plpmtu = self.base;
+        self.enter(PlpmtudState::Base, now);
+        true
+    }
+
+    /// Records that DATA sent on the path was acked
+    pub fn on_data_acked(&mut self) {
+        self.black_hole_count = 0;
+    }
+
+    fn on_probe_lost(&mut self, now: Instant) {
+        if self.probe_count < MAX_PROBES || self.state == PlpmtudState::Error {
+            // the same size is probed again right away
+            self.timer = Some(now);
+            return;
+        }
+        match self.state {
+            PlpmtudState::Base => {
+                self.plpmtu = self.base;
+                self.enter(PlpmtudState::Error, now);
+            }
+            _ => {
+                self.too_big = self.probe_size;
+                self.next_search_probe(now);
+            }
+        }
+    }
+
+    /// Searches up from the current PLPMTU, trying the largest size first
+    fn start_search(&mut self, now: Instant) {
+        self.too_big = self.max + 1;
+        if self.plpmtu >= self.max {
+            self.enter(PlpmtudState::SearchComplete, now);
+            return;
+        }
+        self.state = PlpmtudState::Search;
+        self.set_probe_size(self.max);
+        self.timer = Some(now);
+    }
+
+    /// Probes halfway between the PLPMTU and the smallest size known to fail,
+    /// or completes the search once they are close enough
+    fn next_search_probe(&mut self, now: Instant) {
+        if self.plpmtu >= self.max || self.too_big - self.plpmtu <= SEARCH_GRANULARITY {
+            self.enter(PlpmtudState::SearchComplete, now);
+            return;
+        }
+        let size = (self.plpmtu + (self.too_big - self.plpmtu) / 2) & !3;
+        self.set_probe_size(size);
+        self.timer = Some(now);
+    }
+
+    fn set_probe_size(&mut self, size: u32) {
+        self.probe_size = size;
+        self.probe_count = 0;
+    }
+
+    fn enter(&mut self, state: PlpmtudState, now: Instant) {
+        self.state = state;
+        match state {
+            PlpmtudState::Base | PlpmtudState::Error => {
+                self.set_probe_size(self.base);
+                self.timer = Some(now);
+            }
+            PlpmtudState::SearchComplete => {
+                self.timer = Some(now + PMTU_RAISE_TIMER);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::chunk::{Data, ForwardTsn, IForwardTsn, SkippedMessages};
+use crate::config::PrPolicy;
+use std::collections::BTreeMap;
+use tokio::time::Instant;
+
+/// Decides when a DATA chunk sent with a partial reliability policy
+/// is abandoned (RFC 3758 section 3.5, RFC 7496 section 3)
+#[derive(Clone, Copy, Debug)]
+pub struct Abandonment {
+    policy: PrPolicy,
+    expires_at: Option<Instant>,
+}
+
+impl Abandonment {
+    /// Creates the abandonment rule of a msg handed to us by the ULP at `queued_at`
+    pub fn new(policy: PrPolicy, queued_at: Instant) -> Self {
+        let expires_at = match policy {
+            PrPolicy::Timed(lifetime) => Some(queued_at + lifetime),
+            _ => None,
+        };
+        Self { policy, expires_at }
+    }
+
+    pub fn policy(&self) -> PrPolicy {
+        self.policy
+    }
+
+    /// Returns the priority of a msg sent with the priority policy
+    pub fn priority(&self) -> Option<u16> {
+        match self.policy {
+            PrPolicy::Priority(priority) => Some(priority),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the lifetime of the msg is over
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Returns true if a chunk that has been retransmitted `retransmissions` times
+    /// must not be retransmitted again
+    pub fn is_exhausted(&self, retransmissions: u32) -> bool {
+        match self.policy {
+            PrPolicy::MaxRetransmissions(max) => retransmissions >= max,
+            _ => false,
+        }
+    }
+}
+
+/// Number of msgs and bytes abandoned under a PR policy
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AbandonedCounters {
+    /// msgs abandoned before any of their chunks were sent
+    pub unsent_msgs: u64,
+    pub unsent_bytes: u64,
+    /// msgs abandoned after some of their chunks were sent
+    pub sent_msgs: u64,
+    pub sent_bytes: u64,
+}
+
+/// Abandoned msgs of an association by PR policy (RFC 7496 section 4.4)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AbandonedStats {
+    pub timed: AbandonedCounters,
+    pub max_retransmissions: AbandonedCounters,
+    pub priority: AbandonedCounters,
+}
+
+impl AbandonedStats {
+    /// Counts a msg of `bytes` bytes abandoned under the given policy
+    pub fn record(&mut self, policy: PrPolicy, sent: bool, bytes: usize) {
+        let counters = match policy {
+            PrPolicy::Reliable => return,
+            PrPolicy::Timed(_) => &mut self.timed,
+            PrPolicy::MaxRetransmissions(_) => &mut self.max_retransmissions,
+            PrPolicy::Priority(_) => &mut self.priority,
+        };
+        if sent {
+            counters.sent_msgs += 1;
+            counters.sent_bytes += bytes as u64;
+        } else {
+            counters.unsent_msgs += 1;
+            counters.unsent_bytes += bytes as u64;
+        }
+    }
+}
+
+/// Returns the FORWARD TSN moving the peer's cumulative TSN past the abandoned chunks
+/// at the front of the outstanding chunks, given in TSN order along with whether
+/// each one is abandoned. Returns `None` if the first chunk isn't abandoned.
+/// section 3.5 C1) the new cumulative TSN is the Advanced.Peer.Ack.Point
+/// and C4) every ordered stream with a skipped msg is listed with its largest seq no
+pub fn forward_tsn<'a>(chunks: impl IntoIterator<Item = (&'a Data, bool)>) -> Option<ForwardTsn> {
+    let mut ack_point = None;
+    let mut streams = BTreeMap::new();
+    for (chunk, abandoned) in chunks {
+        if !abandoned {
+            break;
+        }
+        ack_point = Some(chunk.tsn());
+        if !chunk.is_unordered() {
+            // seq nos grow with the TSNs on a stream so the last one is the largest
+            streams.insert(chunk.stream_id(), chunk.stream_seq_no());
+        }
+    }
+    ack_point.map(|ack_point| ForwardTsn::new(ack_point, streams.into_iter().collect()))
+}
+
+/// Returns the I-FORWARD-TSN moving the peer's cumulative TSN past the abandoned chunks
+/// at the front of the outstanding chunks, given in TSN order along with the message id
+/// of each one and whether it is abandoned. Returns `None` if the first chunk isn't abandoned.
+/// RFC 8260 section 2.3.1: unlike FORWARD TSN the unordered msgs are listed too
+pub fn i_forward_tsn<'a>(
+    chunks: impl IntoIterator<Item = (&'a Data, u32, bool)>,
+) -> Option<IForwardTsn> {
+    let mut ack_point = None;
+    let mut streams = BTreeMap::new();
+    for (chunk, message_id, abandoned) in chunks {
+        if !abandoned {
+            break;
+        }
+        ack_point = Some(chunk.tsn());
+        streams.insert((chunk.stream_id(), chunk.is_unordered()), message_id);
+    }
+    ack_point.map(|ack_point| {
+        let streams = streams
+            .into_iter()
+            .map(|((stream_id, unordered), message_id)| SkippedMessages {
+                stream_id,
+                unordered,
+                message_id,
+            })
+            .collect();
+        IForwardTsn::new(ack_point, streams)
+    })
+}
@@ -0,0 +1,312 @@
+use crate::chunk::{Data, IData, SkippedMessages};
+use crate::tsn::tsn_lte;
+use std::collections::{BTreeMap, HashMap};
+
+/// A user msg rebuilt from one or more DATA chunks
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub stream_id: u16,
+    /// for msgs sent in I-DATA chunks this holds the message id
+    pub stream_seq_no: u32,
+    /// true if the msg was sent unordered and is delivered as soon as it is reassembled
+    pub unordered: bool,
+    /// payload protocol identifier set by the sender's ULP
+    pub ppid: u32,
+    pub data: Vec<u8>,
+    /// TSNs of the chunks that carried the msg
+    pub tsns: Vec<u32>,
+}
+
+/// Outcome of adding a fragment to the reassembly queue
+#[derive(Debug, PartialEq)]
+pub enum Reassembled {
+    /// more fragments are needed to complete the msg
+    Pending,
+    /// the fragment completed a msg
+    Complete(Message),
+    /// the msg grew beyond the max msg size and its fragments were thrown away.
+    /// holds the number of bytes that were discarded
+    Discarded(usize),
+}
+
+/// Rebuilds user msgs from fragments carried in TSN-consecutive DATA chunks.
+/// The first fragment has the B flag set, the last one has the E flag set.
+pub struct ReassemblyQueue {
+    fragments: HashMap<u32, Data>,
+    max_message_size: usize,
+    // TSN of the next fragment of a discarded msg that is yet to arrive
+    discarding: Option<u32>,
+}
+
+impl ReassemblyQueue {
+    pub fn new(max_message_size: usize) -> Self {
+        Self {
+            fragments: HashMap::new(),
+            max_message_size,
+            discarding: None,
+        }
+    }
+
+    /// Returns true if no fragments are waiting for reassembly
+    pub fn is_empty(&self) -> bool {
+        self.fragments.is_empty()
+    }
+
+    /// Adds a DATA chunk to the queue and returns the msg it completes, if any
+    pub fn push(&mut self, chunk: Data) -> Reassembled {
+        if chunk.is_beginning() && chunk.is_end() {
+            return self.complete(vec![chunk]);
+        }
+
+        let tsn = chunk.tsn();
+        if self.discarding == Some(tsn) && !chunk.is_beginning() {
+            // the rest of an oversized msg
+            self.discarding = if chunk.is_end() {
+                None
+            } else {
+                Some(tsn.wrapping_add(1))
+            };
+            return Reassembled::Discarded(chunk.payload_len());
+        }
+        self.fragments.insert(tsn, chunk);
+
+        // walk back to the first fragment of the msg
+        let mut first = tsn;
+        loop {
+            match self.fragments.get(&first) {
+                Some(fragment) if fragment.is_beginning() => break,
+                Some(_) => first = first.wrapping_sub(1),
+                None => return Reassembled::Pending,
+            }
+        }
+
+        // and then forward to the last one
+        let mut last = first;
+        let mut len = 0;
+        loop {
+            match self.fragments.get(&last) {
+                Some(fragment) => {
+                    len += fragment.payload_len();
+                    if len > self.max_message_size {
+                        return Reassembled::Discarded(self.discard(first));
+                    }
+                    if fragment.is_end() {
+                        break;
+                    }
+                    last = last.wrapping_add(1);
+                }
+                None => return Reassembled::Pending,
+            }
+        }
+
+        let mut fragments = vec![];
+        let mut tsn = first;
+        loop {
+            fragments.push(self.fragments.remove(&tsn).unwrap());
+            if tsn == last {
+                break;
+            }
+            tsn = tsn.wrapping_add(1);
+        }
+        self.complete(fragments)
+    }
+
+    /// Drops the fragments of the msgs the peer abandoned, i.e. the ones with a TSN
+    /// up to the new cumulative TSN of a FORWARD TSN (RFC 3758 section 3.6).
+    /// Returns the number of bytes they held.
+    pub fn on_forward_tsn(&mut self, new_cumulative_tsn: u32) -> usize {
+        let mut len = 0;
+        self.fragments.retain(|&tsn, fragment| {
+            let abandoned = tsn_lte(tsn, new_cumulative_tsn);
+            if abandoned {
+                len += fragment.payload_len();
+            }
+            !abandoned
+        });
+        if self
+            .discarding
+            .is_some_and(|tsn| tsn_lte(tsn, new_cumulative_tsn))
+        {
+            self.discarding = None;
+        }
+        len
+    }
+
+    fn complete(&self, fragments: Vec<Data>) -> Reassembled {
+        let len = fragments.iter().map(|f| f.payload_len()).sum();
+        if len > self.max_message_size {
+            return Reassembled::Discarded(len);
+        }
+
+        let mut data = Vec::with_capacity(len);
+        for fragment in &fragments {
+            data.extend(fragment.payload());
+        }
+
+        Reassembled::Complete(Message {
+            stream_id: fragments[0].stream_id(),
+            stream_seq_no: fragments[0].stream_seq_no().into(),
+            unordered: fragments[0].is_unordered(),
+            ppid: fragments[0].payload_proto_id(),
+            data,
+            tsns: fragments.iter().map(|f| f.tsn()).collect(),
+        })
+    }
+
+    /// Removes the consecutive fragments starting at `first` up to the end of the msg
+    /// and returns the number of bytes they held
+    fn discard(&mut self, first: u32) -> usize {
+        let mut len = 0;
+        let mut tsn = first;
+        self.discarding = None;
+        while let Some(fragment) = self.fragments.remove(&tsn) {
+            len += fragment.payload_len();
+            if fragment.is_end() {
+                return len;
+            }
+            tsn = tsn.wrapping_add(1);
+        }
+        self.discarding = Some(tsn);
+        len
+    }
+}
+
+/// Fragments of a msg recvd in I-DATA chunks
+#[derive(Default)]
+struct PartialMessage {
+    // fragments by fragment seq no
+    fragments: BTreeMap<u32, IData>,
+    len: usize,
+    // fragment seq no of the last fragment, once it has arrived
+    last_fsn: Option<u32>,
+    // number of fragments recvd so far
+    recvd: u64,
+    // TSN of the latest fragment recvd
+    last_tsn: u32,
+    // true once the msg grew beyond the max msg size
+    discarding: bool,
+}
+
+impl PartialMessage {
+    /// Returns true once every fragment of the msg has been recvd
+    fn all_recvd(&self) -> bool {
+        self.last_fsn
+            .is_some_and(|last_fsn| self.recvd == last_fsn as u64 + 1)
+    }
+}
+
+/// Rebuilds user msgs from fragments carried in I-DATA chunks (RFC 8260 section 2.1).
+/// Fragments are matched by stream, U flag and message id rather than by TSN
+/// so the fragments of different msgs can arrive interleaved.
+pub struct InterleavedReassemblyQueue {
+    msgs: HashMap<(u16, bool, u32), PartialMessage>,
+    max_message_size: usize,
+}
+
+impl InterleavedReassemblyQueue {
+    pub fn new(max_message_size: usize) -> Self {
+        Self {
+            msgs: HashMap::new(),
+            max_message_size,
+        }
+    }
+
+    /// Returns true if no fragments are waiting for reassembly
+    pub fn is_empty(&self) -> bool {
+        self.msgs.is_empty()
+    }
+
+    /// Adds an I-DATA chunk to the queue and returns the msg it completes, if any
+    pub fn push(&mut self, chunk: IData) -> Reassembled {
+        let key = (chunk.stream_id(), chunk.is_unordered(), chunk.message_id());
+        let msg = self.msgs.entry(key).or_default();
+        if msg.recvd == 0 || tsn_lte(msg.last_tsn, chunk.tsn()) {
+            msg.last_tsn = chunk.tsn();
+        }
+        msg.recvd += 1;
+        if chunk.is_end() {
+            msg.last_fsn = Some(chunk.fragment_seq_no());
+        }
+
+        let outcome = if msg.discarding {
+            // the rest of an oversized msg
+            Reassembled::Discarded(chunk.payload_len())
+        } else if msg.len + chunk.payload_len() > self.max_message_size {
+            let len = msg.len + chunk.payload_len();
+            msg.discarding = true;
+            msg.fragments.clear();
+            msg.len = 0;
+            Reassembled::Discarded(len)
+        } else {
+            msg.len += chunk.payload_len();
+            msg.fragments.insert(chunk.fragment_seq_no(), chunk);
+            Reassembled::Pending
+        };
+        if !msg.all_recvd() {
+            return outcome;
+        }
+
+        let msg = self.msgs.remove(&key).unwrap();
+        if msg.discarding {
+            return outcome;
+        }
+        let (stream_id, unordered, message_id) = key;
+        let ppid = msg
+            .fragments
+            .values()
+            .next()
+            .and_then(|first| first.payload_proto_id())
+            .unwrap_or_default();
+        let mut data = Vec::with_capacity(msg.len);
+        for fragment in msg.fragments.values() {
+            data.extend(fragment.payload());
+        }
+        Reassembled::Complete(Message {
+            stream_id,
+            stream_seq_no: message_id,
+            unordered,
+            ppid,
+            data,
+            tsns: msg.fragments.values().map(|f| f.tsn()).collect(),
+        })
+    }
+
+    /// Drops the fragments with a TSN up to the new cumulative TSN of an I-FORWARD-TSN
+    /// and the msgs it skips as the peer abandoned them (RFC 8260 section 2.3.2).
+    /// An oversized msg being discarded is dropped once its fragments are all covered.
+    /// Returns the number of bytes they held.
+    pub fn on_forward_tsn(
+        &mut self,
+        new_cumulative_tsn: u32,
+        skipped: &[SkippedMessages],
+    ) -> usize {
+        let mut len = 0;
+        self.msgs
+            .retain(|&(stream_id, unordered, message_id), msg| {
+                // an ordered stream skips every msg up to the given one
+                let abandoned = skipped.iter().any(|s| {
+                    s.stream_id == stream_id
+                        && s.unordered == unordered
+                        && if unordered {
+                            s.message_id == message_id
+                        } else {
+                            tsn_lte(message_id, s.message_id)
+                        }
+                });
+                if abandoned || (msg.discarding && tsn_lte(msg.last_tsn, new_cumulative_tsn)) {
+                    len += msg.len;
+                    return false;
+                }
+                msg.fragments.retain(|_, fragment| {
+                    let abandoned = tsn_lte(fragment.tsn(), new_cumulative_tsn);
+                    if abandoned {
+                        len += fragment.payload_len();
+                    }
+                    !abandoned
+                });
+                msg.len = msg.fragments.values().map(|f| f.payload_len()).sum();
+                msg.discarding || !msg.fragments.is_empty()
+            });
+        len
+    }
+}
@@ -0,0 +1,148 @@
+use crate::chunk::{ReConfig, ReconfigRequest, ReconfigResponse, ReconfigResult};
+
+/// Chunk type of the RE-CONFIG chunk, listed in the Supported Extensions param
+pub const RECONFIG_CHUNK_TYPE: u8 = 0x82;
+
+/// Numbers the reconfiguration requests sent to the peer and matches the
+/// responses that come back (RFC 6525 section 5.1).
+/// Only one RE-CONFIG with requests can be outstanding at a time.
+pub struct ReconfigSender {
+    // seq no of the next request
+    seq_no: u32,
+    // requests waiting for a response
+    outstanding: Vec<ReconfigRequest>,
+    // requests that got a final response along with it
+    results: Vec<(ReconfigRequest, ReconfigResponse)>,
+    // true if the peer said it is still working on a request
+    in_progress: bool,
+}
+
+impl ReconfigSender {
+    /// Creates a sender whose seq nos start from our initial TSN
+    pub fn new(initial_tsn: u32) -> Self {
+        Self {
+            seq_no: initial_tsn,
+            outstanding: vec![],
+            results: vec![],
+            in_progress: false,
+        }
+    }
+
+    /// Numbers the given requests and returns the RE-CONFIG carrying them.
+    /// Returns `None` if some request hasn't got a response yet.
+    pub fn send(&mut self, requests: Vec<ReconfigRequest>) -> Option<ReConfig> {
+        if !self.outstanding.is_empty() {
+            return None;
+        }
+        self.outstanding = requests
+            .into_iter()
+            .map(|request| {
+                let request = request.with_seq_no(self.seq_no);
+                self.seq_no = self.seq_no.wrapping_add(1);
+                request
+            })
+            .collect();
+        self.results.clear();
+        Some(self.retransmission())
+    }
+
+    /// Returns the requests waiting for a response
+    pub fn outstanding(&self) -> &[ReconfigRequest] {
+        &self.outstanding
+    }
+
+    /// Returns the RE-CONFIG retransmitting the outstanding requests
+    pub fn retransmission(&self) -> ReConfig {
+        ReConfig::new(vec![], self.outstanding.clone())
+    }
+
+    /// Matches a response with the outstanding request it answers.
+    /// Returns the request if it is complete. A request answered with
+    /// In Progress stays outstanding and is retransmitted (section 5.2.7).
+    pub fn on_response(&mut self, response: &ReconfigResponse) -> Option<ReconfigRequest> {
+        let idx = self
+            .outstanding
+            .iter()
+            .position(|request| request.seq_no() == response.seq_no)?;
+        if response.result == ReconfigResult::InProgress {
+            self.in_progress = true;
+            return None;
+        }
+        let request = self.outstanding.remove(idx);
+        self.results.push((request.clone(), response.clone()));
+        Some(request)
+    }
+
+    /// Returns true if the peer said it is still working on a request
+    /// since the last time this was called
+    pub fn take_in_progress(&mut self) -> bool {
+        std::mem::take(&mut self.in_progress)
+    }
+
+    /// Takes the requests that got a final response so far
+    pub fn take_results(&mut self) -> Vec<(ReconfigRequest, ReconfigResponse)> {
+        std::mem::take(&mut self.results)
+    }
+}
+
+/// What to do with a reconfiguration request recvd from the peer
+#[derive(Debug)]
+pub enum ReconfigVerdict {
+    /// the request is the next one in sequence and must be processed
+    New,
+    /// the request was processed before and its response got lost
+    Retransmitted(ReconfigResponse),
+    /// the request is out of sequence
+    BadSeqNo,
+}
+
+/// Processes the reconfiguration requests of the peer in seq no order (RFC 6525 section 5.2.1)
+pub struct ReconfigReceiver {
+    // seq no of the next request
+    seq_no: u32,
+    // responses to the last two requests processed, the latest last
+    last_responses: Vec<ReconfigResponse>,
+}
+
+impl ReconfigReceiver {
+    /// Creates a receiver expecting the seq nos of the peer
+    /// to start from its initial TSN
+    pub fn new(peer_initial_tsn: u32) -> Self {
+        Self {
+            seq_no: peer_initial_tsn,
+            last_responses: vec![],
+        }
+    }
+
+    /// Returns the seq no of the last request processed
+    pub fn last_seq_no(&self) -> u32 {
+        self.seq_no.wrapping_sub(1)
+    }
+
+    pub fn on_request(&self, request: &ReconfigRequest) -> ReconfigVerdict {
+        if request.seq_no() == self.seq_no {
+            return ReconfigVerdict::New;
+        }
+        match self
+            .last_responses
+            .iter()
+            .find(|response| response.seq_no == request.seq_no())
+        {
+            Some(response) => ReconfigVerdict::Retransmitted(response.clone()),
+            None => ReconfigVerdict::BadSeqNo,
+        }
+    }
+
+    /// Remembers the response sent for the last request processed.
+    /// A request that is still in progress is processed again when it is retransmitted.
+    pub fn on_processed(&mut self, response: ReconfigResponse) {
+        if response.result == ReconfigResult::InProgress {
+            return;
+        }
+        self.seq_no = response.seq_no.wrapping_add(1);
+        if self.last_responses.len() == 2 {
+            self.last_responses.remove(0);
+        }
+        self.last_responses.push(response);
+    }
+}
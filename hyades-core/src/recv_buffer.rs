@@ -0,0 +1,222 @@
+use crate::chunk::{NrSack, Sack};
+use crate::tsn::{tsn_lt, tsn_lte};
+use std::cmp;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+/// What happened to an incoming DATA chunk
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DataVerdict {
+    /// the chunk was accepted and its payload is now buffered
+    Accepted,
+    /// the chunk was received before
+    Duplicate,
+    /// there is no room for the chunk in the receive buffer
+    /// or its TSN is too far ahead of the cumulative TSN to be acked
+    Dropped,
+}
+
+/// Accounts for the local receive buffer: which TSNs have been received,
+/// how many bytes are waiting to be delivered to the ULP
+/// and what window to advertise to the peer (section 6.2)
+pub struct RecvBuffer {
+    capacity: u32,
+    // bytes received but not yet delivered to the ULP
+    buffered: u32,
+    mtu: u32,
+    cumulative_tsn: u32,
+    // TSNs received after a gap, i.e. larger than the cumulative TSN + 1
+    out_of_order: BTreeSet<u32>,
+    // out of order TSNs whose data has been delivered to the ULP
+    // so it will never be reneged on
+    non_renegable: BTreeSet<u32>,
+    // duplicates received since the last SACK
+    dup_tsns: Vec<u32>,
+    // the window advertised in the last SACK
+    advertised: u32,
+}
+
+impl RecvBuffer {
+    pub fn new(capacity: u32, mtu: u32) -> Self {
+        Self {
+            capacity,
+            buffered: 0,
+            mtu,
+            cumulative_tsn: 0,
+            out_of_order: BTreeSet::new(),
+            non_renegable: BTreeSet::new(),
+            dup_tsns: vec![],
+            advertised: capacity,
+        }
+    }
+
+    /// Sets the initial TSN announced by the peer in its INIT/INIT ACK
+    pub fn set_initial_tsn(&mut self, initial_tsn: u32) {
+        self.cumulative_tsn = initial_tsn.wrapping_sub(1);
+        self.out_of_order.clear();
+        self.non_renegable.clear();
+    }
+
+    /// Returns the last TSN up to which all DATA has been received
+    pub fn cumulative_tsn(&self) -> u32 {
+        self.cumulative_tsn
+    }
+
+    /// Returns the number of bytes waiting to be delivered to the ULP
+    pub fn buffered(&self) -> u32 {
+        self.buffered
+    }
+
+    /// Decides whether the DATA chunk with the given TSN and payload length is accepted
+    pub fn on_data(&mut self, tsn: u32, len: u32) -> DataVerdict {
+        if tsn_lte(tsn, self.cumulative_tsn) || self.out_of_order.contains(&tsn) {
+            self.dup_tsns.push(tsn);
+            return DataVerdict::Duplicate;
+        }
+
+        // gap ack blocks are 16 bit offsets from the cumulative TSN
+        // so a TSN further ahead can't be acked
+        if tsn.wrapping_sub(self.cumulative_tsn) > u16::MAX as u32 {
+            return DataVerdict::Dropped;
+        }
+
+        // section 6.2: when there is no room for new data, drop any DATA chunk with
+        // a TSN larger than the largest received so far. chunks filling holes are
+        // still accepted so the cumulative TSN can move forward
+        if self.buffered + len > self.capacity && tsn_lt(self.highest_tsn(), tsn) {
+            return DataVerdict::Dropped;
+        }
+
+        self.buffered += len;
+        if tsn == self.cumulative_tsn.wrapping_add(1) {
+            self.cumulative_tsn = tsn;
+            self.advance_cumulative_tsn();
+        } else {
+            self.out_of_order.insert(tsn);
+        }
+
+        DataVerdict::Accepted
+    }
+
+    /// Moves the cumulative TSN forward to the one carried by a FORWARD TSN,
+    /// treating the TSNs that were skipped as received (RFC 3758 section 3.6).
+    /// Returns false if the cumulative TSN is already past it.
+    pub fn on_forward_tsn(&mut self, new_cumulative_tsn: u32) -> bool {
+        if tsn_lte(new_cumulative_tsn, self.cumulative_tsn) {
+            return false;
+        }
+        self.cumulative_tsn = new_cumulative_tsn;
+        self.out_of_order
+            .retain(|&tsn| tsn_lt(new_cumulative_tsn, tsn));
+        self.non_renegable
+            .retain(|&tsn| tsn_lt(new_cumulative_tsn, tsn));
+        self.advance_cumulative_tsn();
+        true
+    }
+
+    /// Moves the cumulative TSN past the out of order TSNs that follow it
+    fn advance_cumulative_tsn(&mut self) {
+        while self
+            .out_of_order
+            .remove(&self.cumulative_tsn.wrapping_add(1))
+        {
+            self.cumulative_tsn = self.cumulative_tsn.wrapping_add(1);
+            self.non_renegable.remove(&self.cumulative_tsn);
+        }
+    }
+
+    /// Marks the out of order TSNs of a msg that was delivered to the ULP as non-renegable
+    pub fn on_msg_delivered(&mut self, tsns: &[u32]) {
+        for tsn in tsns {
+            if self.out_of_order.contains(tsn) {
+                self.non_renegable.insert(*tsn);
+            }
+        }
+    }
+
+    /// Releases `len` bytes that have been delivered to the ULP
+    pub fn on_delivered(&mut self, len: u32) {
+        self.buffered = self.buffered.saturating_sub(len);
+    }
+
+    /// Returns the window to advertise to the peer.
+    /// A window that reopens is only advertised once it has grown by at least
+    /// min(buffer size / 2, MTU) to avoid the silly window syndrome.
+    pub fn a_rwnd(&mut self) -> u32 {
+        let free = self.capacity.saturating_sub(self.buffered);
+        if free < self.advertised || free - self.advertised >= cmp::min(self.capacity / 2, self.mtu)
+        {
+            self.advertised = free;
+        }
+        self.advertised
+    }
+
+    /// Builds a SACK acknowledging everything received so far
+    pub fn sack(&mut self) -> Sack {
+        let gaps = self.gap_ack_blocks();
+        let dup_tsns: Vec<u32> = self.dup_tsns.drain(..).collect();
+        let a_rwnd = self.a_rwnd();
+
+        Sack::new(
+            self.cumulative_tsn,
+            a_rwnd,
+            gaps.len() as u16,
+            dup_tsns.len() as u16,
+            if gaps.is_empty() { None } else { Some(gaps) },
+            if dup_tsns.is_empty() {
+                None
+            } else {
+                Some(dup_tsns)
+            },
+        )
+    }
+
+    /// Builds an NR-SACK acknowledging everything received so far,
+    /// with the non-renegable TSNs in the NR gap ack blocks
+    pub fn nr_sack(&mut self) -> NrSack {
+        let renegable: Vec<u32> = self
+            .out_of_order
+            .difference(&self.non_renegable)
+            .copied()
+            .collect();
+        let gaps = self.to_gap_ack_blocks(renegable.iter());
+        let nr_gaps = self.to_gap_ack_blocks(self.non_renegable.iter());
+        let dup_tsns = self.dup_tsns.drain(..).collect();
+        let a_rwnd = self.a_rwnd();
+
+        NrSack::new(self.cumulative_tsn, a_rwnd, gaps, nr_gaps, dup_tsns)
+    }
+
+    fn highest_tsn(&self) -> u32 {
+        self.out_of_order
+            .iter()
+            .map(|tsn| tsn.wrapping_sub(self.cumulative_tsn))
+            .max()
+            .map_or(self.cumulative_tsn, |offset| {
+                self.cumulative_tsn.wrapping_add(offset)
+            })
+    }
+
+    /// Returns the gap ack blocks as offsets from the cumulative TSN
+    fn gap_ack_blocks(&self) -> Vec<(u16, u16)> {
+        self.to_gap_ack_blocks(self.out_of_order.iter())
+    }
+
+    /// Returns the gap ack blocks covering the given out of order TSNs
+    fn to_gap_ack_blocks<'a>(&self, tsns: impl Iterator<Item = &'a u32>) -> Vec<(u16, u16)> {
+        // offsets that don't fit in 16 bits can't be acked
+        let mut offsets: Vec<u16> = tsns
+            .filter_map(|tsn| u16::try_from(tsn.wrapping_sub(self.cumulative_tsn)).ok())
+            .collect();
+        offsets.sort_unstable();
+
+        let mut blocks: Vec<(u16, u16)> = vec![];
+        for offset in offsets {
+            match blocks.last_mut() {
+                Some((_, end)) if end.checked_add(1) == Some(offset) => *end = offset,
+                _ => blocks.push((offset, offset)),
+            }
+        }
+        blocks
+    }
+}
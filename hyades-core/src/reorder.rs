@@ -0,0 +1,173 @@
+use crate::reassembly::Message;
+use std::collections::HashMap;
+
+/// Ordering state of a single inbound stream
+struct InboundStream {
+    // the stream seq no, or message id, of the next msg to be delivered
+    next_ssn: u32,
+    // complete msgs that arrived ahead of `next_ssn`
+    pending: HashMap<u32, Message>,
+}
+
+/// Holds back msgs that arrive out of order on a stream until
+/// the msgs before them have been delivered (section 6.6).
+/// Every stream is ordered independently so a missing msg on one stream
+/// doesn't block the delivery of msgs on the others.
+pub struct ReorderQueue {
+    streams: Vec<InboundStream>,
+    // msgs sent in I-DATA chunks are ordered by their 32 bit message id
+    // instead of a 16 bit stream seq no
+    interleaving: bool,
+}
+
+impl ReorderQueue {
+    /// Creates a queue for `num_streams` inbound streams
+    pub fn new(num_streams: u16) -> Self {
+        Self {
+            streams: (0..num_streams)
+                .map(|_| InboundStream {
+                    next_ssn: 0,
+                    pending: HashMap::new(),
+                })
+                .collect(),
+            interleaving: false,
+        }
+    }
+
+    /// Orders msgs by the message id of the I-DATA chunks that carried them
+    /// instead of their stream seq no (RFC 8260 section 2.1)
+    pub fn set_interleaving(&mut self, interleaving: bool) {
+        self.interleaving = interleaving;
+    }
+
+    /// Returns the number of inbound streams
+    pub fn num_streams(&self) -> u16 {
+        self.streams.len() as u16
+    }
+
+    /// Returns true if the peer is allowed to send DATA on the given stream
+    pub fn is_valid_stream(&self, stream_id: u16) -> bool {
+        (stream_id as usize) < self.streams.len()
+    }
+
+    /// Adds a complete msg to the queue and returns the msgs,
+    /// in stream seq no order, that can now be delivered to the ULP.
+    /// Unordered msgs are handed back right away.
+    pub fn push(&mut self, msg: Message) -> Vec<Message> {
+        if msg.unordered {
+            return vec![msg];
+        }
+
+        let interleaving = self.interleaving;
+        let stream = match self.streams.get_mut(msg.stream_id as usize) {
+            Some(stream) => stream,
+            None => return vec![],
+        };
+
+        // a msg before the next one to deliver has been delivered or skipped already
+        if seq_lt(msg.stream_seq_no, stream.next_ssn, interleaving) {
+            return vec![];
+        }
+        if msg.stream_seq_no != stream.next_ssn {
+            stream.pending.insert(msg.stream_seq_no, msg);
+            return vec![];
+        }
+
+        let mut msgs = vec![msg];
+        stream.next_ssn = next_seq(stream.next_ssn, interleaving);
+        while let Some(msg) = stream.pending.remove(&stream.next_ssn) {
+            msgs.push(msg);
+            stream.next_ssn = next_seq(stream.next_ssn, interleaving);
+        }
+        msgs
+    }
+
+    /// Skips the msgs the peer abandoned on a stream, up to and including
+    /// the given stream seq no (RFC 3758 section 3.6), or message id (RFC 8260 section 2.3.2).
+    /// Returns the msgs that can now be delivered to the ULP.
+    pub fn skip(&mut self, stream_id: u16, stream_seq_no: u32) -> Vec<Message> {
+        let interleaving = self.interleaving;
+        let stream = match self.streams.get_mut(stream_id as usize) {
+            Some(stream) => stream,
+            None => return vec![],
+        };
+
+        // the msgs were delivered or skipped already
+        if !seq_lte(stream.next_ssn, stream_seq_no, interleaving) {
+            return vec![];
+        }
+
+        // the msgs that did arrive before the skipped seq no are still delivered.
+        // message ids can be far apart so the seq nos in between aren't walked one by one
+        let next_ssn = stream.next_ssn;
+        let mut arrived: Vec<u32> = stream
+            .pending
+            .keys()
+            .filter(|&&ssn| seq_lte(ssn, stream_seq_no, interleaving))
+            .cloned()
+            .collect();
+        arrived.sort_by_key(|&ssn| seq_distance(next_ssn, ssn, interleaving));
+        let mut msgs: Vec<Message> = arrived
+            .iter()
+            .filter_map(|ssn| stream.pending.remove(ssn))
+            .collect();
+
+        stream.next_ssn = next_seq(stream_seq_no, interleaving);
+        while let Some(msg) = stream.pending.remove(&stream.next_ssn) {
+            msgs.push(msg);
+            stream.next_ssn = next_seq(stream.next_ssn, interleaving);
+        }
+        msgs
+    }
+
+    /// Resets the seq nos of the given streams, all of them if none are given
+    /// (RFC 6525 section 5.2.2). Msgs held back on a reset stream are dropped.
+    pub fn reset(&mut self, stream_ids: &[u16]) {
+        for (stream_id, stream) in self.streams.iter_mut().enumerate() {
+            if stream_ids.is_empty() || stream_ids.contains(&(stream_id as u16)) {
+                stream.next_ssn = 0;
+                stream.pending.clear();
+            }
+        }
+    }
+
+    /// Adds `num_streams` inbound streams (RFC 6525 section 5.2.5)
+    pub fn add_streams(&mut self, num_streams: u16) {
+        let total = self.streams.len() + num_streams as usize;
+        self.streams.resize_with(total, || InboundStream {
+            next_ssn: 0,
+            pending: HashMap::new(),
+        });
+    }
+}
+
+/// Returns true if the seq no `a` comes before or is the same as `b`.
+/// Stream seq nos wrap around after 2^16 - 1 and message ids after 2^32 - 1,
+/// just like TSNs do.
+fn seq_lte(a: u32, b: u32, interleaving: bool) -> bool {
+    let half = if interleaving { 1 << 31 } else { 1 << 15 };
+    seq_distance(a, b, interleaving) < half
+}
+
+/// Returns how far the seq no `b` is ahead of `a`
+fn seq_distance(a: u32, b: u32, interleaving: bool) -> u32 {
+    if interleaving {
+        b.wrapping_sub(a)
+    } else {
+        (b as u16).wrapping_sub(a as u16) as u32
+    }
+}
+
+/// Returns true if the seq no `a` comes before `b`
+fn seq_lt(a: u32, b: u32, interleaving: bool) -> bool {
+    a != b && seq_lte(a, b, interleaving)
+}
+
+/// Returns the seq no following `seq`
+fn next_seq(seq: u32, interleaving: bool) -> u32 {
+    if interleaving {
+        seq.wrapping_add(1)
+    } else {
+        (seq as u16).wrapping_add(1) as u32
+    }
+}
@@ -0,0 +1,103 @@
+use std::cmp;
+use std::time::Duration;
+
+pub const RTO_INITIAL: Duration = Duration::from_secs(3);
+pub const RTO_MIN: Duration = Duration::from_secs(1);
+pub const RTO_MAX: Duration = Duration::from_secs(60);
+const RTO_ALPHA: f64 = 1.0 / 8.0;
+const RTO_BETA: f64 = 1.0 / 4.0;
+// granularity of the clock used to measure rtts
+const CLOCK_GRANULARITY: Duration = Duration::from_millis(1);
+
+/// Snapshot of the round trip time estimates of a destination
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SrttReport {
+    /// smoothed round trip time; `None` until the first measurement is made
+    pub srtt: Option<Duration>,
+    /// round trip time variation
+    pub rttvar: Duration,
+    /// current retransmission timeout
+    pub rto: Duration,
+}
+
+/// Keeps track of SRTT/RTTVAR and computes the RTO from them
+/// as described in section 6.3.1
+#[derive(Clone, Debug)]
+pub struct Rto {
+    srtt: Option<Duration>,
+    rttvar: Duration,
+    rto: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl Rto {
+    /// Creates a new calculator.
+    /// 6.3.1 C1) until an RTT measurement has been made the RTO is set to RTO.Initial
+    pub fn new(initial: Duration, min: Duration, max: Duration) -> Self {
+        Self {
+            srtt: None,
+            rttvar: Duration::from_secs(0),
+            rto: cmp::min(cmp::max(initial, min), max),
+            min,
+            max,
+        }
+    }
+
+    /// Returns the current retransmission timeout
+    pub fn rto(&self) -> Duration {
+        self.rto
+    }
+
+    /// Returns the current smoothed round trip time
+    pub fn srtt(&self) -> Option<Duration> {
+        self.srtt
+    }
+
+    /// Returns the current round trip time variation
+    pub fn rttvar(&self) -> Duration {
+        self.rttvar
+    }
+
+    /// Updates the estimates with a new RTT measurement `r`.
+    /// Callers must make sure `r` wasn't taken from a retransmitted chunk (Karn's algorithm).
+    /// Since the RTO is recomputed from the estimates, any backoff applied before is undone.
+    pub fn on_measurement(&mut self, r: Duration) {
+        match self.srtt {
+            None => {
+                // C2)
+                self.srtt = Some(r);
+                self.rttvar = r / 2;
+            }
+            Some(srtt) => {
+                // C3)
+                let delta = srtt.abs_diff(r);
+                self.rttvar = self.rttvar.mul_f64(1.0 - RTO_BETA) + delta.mul_f64(RTO_BETA);
+                self.srtt = Some(srtt.mul_f64(1.0 - RTO_ALPHA) + r.mul_f64(RTO_ALPHA));
+            }
+        }
+
+        // C4) G is the clock granularity
+        let rto = self.srtt.unwrap() + cmp::max(CLOCK_GRANULARITY, 4 * self.rttvar);
+        self.rto = self.clamp(rto);
+    }
+
+    /// Doubles the RTO after a T3-rtx expiration (6.3.3 E2)
+    pub fn backoff(&mut self) {
+        self.rto = self.clamp(self.rto * 2);
+    }
+
+    /// Returns the current estimates
+    pub fn report(&self) -> SrttReport {
+        SrttReport {
+            srtt: self.srtt,
+            rttvar: self.rttvar,
+            rto: self.rto,
+        }
+    }
+
+    /// C6) and C7): RTO is kept between RTO.Min and RTO.Max
+    fn clamp(&self, rto: Duration) -> Duration {
+        cmp::min(cmp::max(rto, self.min), self.max)
+    }
+}
@@ -0,0 +1,185 @@
+use crate::error::SCTPError;
+
+// scales the finish times of the fair queueing schedulers
+// so that the bytes sent divided by the weight aren't rounded away
+const WEIGHT_SCALE: u64 = 1 << 16;
+
+/// The stream schedulers of RFC 8260 section 3 deciding which stream
+/// the next DATA chunk is sent on
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StreamScheduler {
+    /// msgs are sent in the order the ULP sent them
+    #[default]
+    Fcfs,
+    /// the streams take turns, one msg each
+    RoundRobin,
+    /// the streams take turns, one packet each
+    RoundRobinPerPacket,
+    /// the streams with the lowest priority value go first,
+    /// taking turns when they have the same priority
+    Priority,
+    /// every stream gets the same share of the bandwidth
+    FairBandwidth,
+    /// every stream gets a share of the bandwidth in proportion to its weight
+    WeightedFairQueueing,
+}
+
+/// Scheduling state of an outbound stream
+#[derive(Clone, Copy)]
+struct StreamState {
+    priority: u16,
+    weight: u16,
+    // virtual time at which the last chunk sent on the stream finished
+    finish: u64,
+}
+
+impl Default for StreamState {
+    fn default() -> Self {
+        Self {
+            priority: 0,
+            weight: 1,
+            finish: 0,
+        }
+    }
+}
+
+/// Picks the stream whose queued data goes out next.
+/// Without I-DATA the fragments of a msg must get consecutive TSNs, so a stream
+/// keeps being picked until the msg it started is sent (RFC 8260 section 1).
+pub struct Scheduler {
+    kind: StreamScheduler,
+    // true if msgs of different streams may be interleaved (RFC 8260 section 2)
+    interleaving: bool,
+    streams: Vec<StreamState>,
+    // start-time fair queueing: the start time of the chunk sent last
+    virtual_time: u64,
+    // stream of the last chunk sent
+    last: Option<u16>,
+    // stream of the msg whose last fragment hasn't been sent yet
+    current: Option<u16>,
+}
+
+impl Scheduler {
+    pub fn new(kind: StreamScheduler, num_streams: u16) -> Self {
+        Self {
+            kind,
+            interleaving: false,
+            streams: vec![StreamState::default(); num_streams as usize],
+            virtual_time: 0,
+            last: None,
+            current: None,
+        }
+    }
+
+    /// Allows the fragments of msgs on different streams to be interleaved
+    pub fn set_interleaving(&mut self, interleaving: bool) {
+        self.interleaving = interleaving;
+    }
+
+    pub fn interleaving(&self) -> bool {
+        self.interleaving
+    }
+
+    /// Adds streams with the default priority and weight
+    pub fn add_streams(&mut self, num_streams: u16) {
+        let len = self.streams.len() + num_streams as usize;
+        self.streams.resize(len, StreamState::default());
+    }
+
+    /// Sets the priority of a stream for the priority scheduler.
+    /// The lower the value, the higher the priority.
+    pub fn set_priority(&mut self, stream_id: u16, priority: u16) -> Result<(), SCTPError> {
+        self.stream_mut(stream_id)?.priority = priority;
+        Ok(())
+    }
+
+    /// Sets the weight of a stream for the weighted fair queueing scheduler.
+    /// A weight of 0 counts as 1.
+    pub fn set_weight(&mut self, stream_id: u16, weight: u16) -> Result<(), SCTPError> {
+        self.stream_mut(stream_id)?.weight = weight.max(1);
+        Ok(())
+    }
+
+    fn stream_mut(&mut self, stream_id: u16) -> Result<&mut StreamState, SCTPError> {
+        self.streams
+            .get_mut(stream_id as usize)
+            .ok_or(SCTPError::InvalidStreamId)
+    }
+
+    /// Picks one of the streams that have data queued.
+    /// `streams` are listed in the order their oldest queued chunk was queued in.
+    pub fn pick(&self, streams: &[u16]) -> Option<u16> {
+        let continues_msg = !self.interleaving
+            || matches!(
+                self.kind,
+                StreamScheduler::Fcfs | StreamScheduler::RoundRobin
+            );
+        if continues_msg {
+            if let Some(current) = self.current.filter(|current| streams.contains(current)) {
+                return Some(current);
+            }
+        }
+
+        match self.kind {
+            StreamScheduler::Fcfs => streams.first().copied(),
+            StreamScheduler::RoundRobin | StreamScheduler::RoundRobinPerPacket => {
+                self.next_in_turn(streams.iter().copied())
+            }
+            StreamScheduler::Priority => {
+                let highest = streams
+                    .iter()
+                    .map(|&stream_id| self.state(stream_id).priority)
+                    .min()?;
+                self.next_in_turn(
+                    streams
+                        .iter()
+                        .copied()
+                        .filter(|&stream_id| self.state(stream_id).priority == highest),
+                )
+            }
+            StreamScheduler::FairBandwidth | StreamScheduler::WeightedFairQueueing => streams
+                .iter()
+                .copied()
+                .min_by_key(|&stream_id| self.start_time(stream_id)),
+        }
+    }
+
+    /// Records that a chunk of `bytes` bytes was sent on the stream.
+    /// `end` is true if it was the last fragment of its msg.
+    pub fn on_sent(&mut self, stream_id: u16, bytes: usize, end: bool) {
+        self.last = Some(stream_id);
+        self.current = if end { None } else { Some(stream_id) };
+
+        let start = self.start_time(stream_id);
+        let weight = match self.kind {
+            StreamScheduler::WeightedFairQueueing => self.state(stream_id).weight as u64,
+            _ => 1,
+        };
+        self.virtual_time = start;
+        if let Some(stream) = self.streams.get_mut(stream_id as usize) {
+            stream.finish = start + bytes as u64 * WEIGHT_SCALE / weight;
+        }
+    }
+
+    /// Returns the first stream after the one sent on last, wrapping around
+    fn next_in_turn(&self, streams: impl Iterator<Item = u16> + Clone) -> Option<u16> {
+        let after_last = |stream_id: &u16| self.last.is_none_or(|last| *stream_id > last);
+        streams
+            .clone()
+            .filter(after_last)
+            .min()
+            .or_else(|| streams.min())
+    }
+
+    /// A stream that has been idle starts over from the current virtual time
+    fn start_time(&self, stream_id: u16) -> u64 {
+        self.state(stream_id).finish.max(self.virtual_time)
+    }
+
+    fn state(&self, stream_id: u16) -> StreamState {
+        self.streams
+            .get(stream_id as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+}
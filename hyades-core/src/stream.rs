@@ -1,79 +1,317 @@
 use crate::error::SCTPError;
-use log::info;
+use std::future::poll_fn;
+use std::io;
 use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::Poll;
+use tokio::io::Interest;
 use tokio::net::UdpSocket;
 
+// largest payload a udp datagram can carry
+const MAX_DATAGRAM_SIZE: usize = 65535;
+// the ECN field is made up of the 2 low bits of the TOS/traffic class (RFC 3168 section 5)
+const ECN_MASK: u8 = 0b11;
+const ECT0: u8 = 0b10;
+const CE: u8 = 0b11;
+
+/// UDP sockets bound to every local address of an association
 pub struct Stream {
-    sock: UdpSocket,
+    socks: Vec<UdpSocket>,
+    // true if outgoing datagrams are marked ECN capable
+    ecn: bool,
+    // true if the last datagram recvd had the CE codepoint set
+    ce_marked: AtomicBool,
 }
 
 impl Stream {
-    /// Creates a new UDP stream
-    pub async fn new(local_addr: impl AsRef<str>) -> Result<Self, SCTPError> {
-        let local_sockaddr: SocketAddr = local_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidLocalAddress)?;
-
-        let sock = UdpSocket::bind(local_sockaddr)
-            .await
-            .map_err(|_| SCTPError::SocketBindError)?;
+    /// Creates a new UDP stream bound to the given local addresses
+    pub async fn bind(local_addrs: &[SocketAddr]) -> Result<Self, SCTPError> {
+        let mut socks = Vec::with_capacity(local_addrs.len());
+        for local_addr in local_addrs {
+            socks.push(bind(*local_addr).await?);
+        }
 
-        Ok(Self { sock })
+        Ok(Self {
+            socks,
+            ecn: false,
+            ce_marked: AtomicBool::new(false),
+        })
     }
 
-    pub async fn connect(self, remote_addr: impl AsRef<str>) -> Result<Stream, SCTPError> {
-        let remote_sockaddr: SocketAddr = remote_addr
-            .as_ref()
-            .parse()
-            .map_err(|_| SCTPError::InvalidRemoteAddress)?;
+    /// Binds one more local addr
+    pub async fn add(&mut self, local_addr: SocketAddr) -> Result<(), SCTPError> {
+        let sock = bind(local_addr).await?;
+        if self.ecn {
+            sockopt::set_ect(&sock, true).map_err(|_| SCTPError::SocketBindError)?;
+        }
+        self.socks.push(sock);
+        Ok(())
+    }
 
-        // Set default remote addr to sent data to/recv data from
-        self.sock
-            .connect(remote_sockaddr)
-            .await
-            .map_err(|_| SCTPError::SocketConnectError)?;
+    /// Marks outgoing datagrams with the ECT(0) codepoint if `ecn` is true
+    pub fn set_ecn(&mut self, ecn: bool) -> Result<(), SCTPError> {
+        for sock in &self.socks {
+            sockopt::set_ect(sock, ecn).map_err(|_| SCTPError::EcnUnavailable)?;
+        }
+        self.ecn = ecn;
+        Ok(())
+    }
 
-        Ok(self)
+    /// Returns true if the last datagram recvd had the CE codepoint set,
+    /// meaning a router on the way was congested
+    pub fn ce_marked(&self) -> bool {
+        self.ce_marked.load(Ordering::Relaxed)
     }
 
-    /// Send data to remote peer
-    pub async fn send(&self, buf: &[u8]) -> Result<(), SCTPError> {
-        self.sock
-            .send(buf)
+    /// Closes the socket bound to the given local addr
+    pub fn remove(&mut self, local_addr: SocketAddr) {
+        self.socks
+            .retain(|sock| sock.local_addr().ok() != Some(local_addr));
+    }
+
+    /// Send data to the given remote addr.
+    /// The data goes out of the first local addr of the same family as the remote addr.
+    pub async fn send_to(&self, buf: &[u8], remote_addr: SocketAddr) -> Result<(), SCTPError> {
+        self.sock_for(remote_addr)?
+            .send_to(buf, remote_addr)
             .await
             .map_err(|_| SCTPError::SocketSendError)?;
         Ok(())
     }
 
-    /// Recv data from remote peer this stream is already connected to
-    pub async fn recv(&self) -> Result<Vec<u8>, SCTPError> {
-        // TODO abhi - set min vec capacity from SCTP RFC
-        let mut buf = [0u8; 1024]; // Vec::new();
+    /// Send data to the given remote addr without the DF bit
+    /// so routers may fragment it if it is larger than the PMTU
+    pub async fn send_fragmentable_to(
+        &self,
+        buf: &[u8],
+        remote_addr: SocketAddr,
+    ) -> Result<(), SCTPError> {
+        let sock = self.sock_for(remote_addr)?;
+        let _ = sockopt::set_df(sock, false);
+        let result = sock.send_to(buf, remote_addr).await;
+        let _ = sockopt::set_df(sock, true);
+        result.map_err(|_| SCTPError::SocketSendError)?;
+        Ok(())
+    }
 
-        let len = self
-            .sock
-            .recv(&mut buf)
-            .await
-            .map_err(|_| SCTPError::SocketRecvError)?;
-        Ok(buf[..len].to_vec())
+    fn sock_for(&self, remote_addr: SocketAddr) -> Result<&UdpSocket, SCTPError> {
+        self.socks
+            .iter()
+            .find(|sock| {
+                sock.local_addr()
+                    .is_ok_and(|addr| addr.is_ipv4() == remote_addr.is_ipv4())
+            })
+            .or_else(|| self.socks.first())
+            .ok_or(SCTPError::SocketSendError)
     }
 
-    /// Recv data from a remote peer this stream isn't connected to
+    /// Recv data from a remote peer on any of the local addrs
     pub async fn recv_from(&self) -> Result<(Vec<u8>, SocketAddr), SCTPError> {
-        // TODO abhi - set min vec capacity from SCTP RFC
-        let mut buf = [0u8; 1024]; // Vec::new();
-        let (len, addr) = self
-            .sock
-            .recv_from(&mut buf)
-            .await
-            .map_err(|_| SCTPError::SocketRecvError)?;
+        // kept on the heap as it would otherwise blow up the size of every future awaiting it
+        let mut buf = vec![0u8; MAX_DATAGRAM_SIZE];
 
-        self.sock
-            .connect(addr)
-            .await
-            .map_err(|_| SCTPError::SocketConnectError)?;
+        let (len, addr, tos) = poll_fn(|cx| {
+            for sock in &self.socks {
+                // readiness is cleared when the datagram turns out to be gone already
+                while let Poll::Ready(result) = sock.poll_recv_ready(cx) {
+                    if let Err(e) = result {
+                        return Poll::Ready(Err(e));
+                    }
+                    match sock.try_io(Interest::READABLE, || sockopt::recv_from(sock, &mut buf)) {
+                        Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                        result => return Poll::Ready(result),
+                    }
+                }
+            }
+            Poll::Pending
+        })
+        .await
+        .map_err(|_| SCTPError::SocketRecvError)?;
+
+        self.ce_marked
+            .store(tos & ECN_MASK == CE, Ordering::Relaxed);
+        buf.truncate(len);
+        Ok((buf, addr))
+    }
+}
+
+async fn bind(local_addr: SocketAddr) -> Result<UdpSocket, SCTPError> {
+    let sock = UdpSocket::bind(local_addr)
+        .await
+        .map_err(|_| SCTPError::SocketBindError)?;
+    // without the TOS of recvd datagrams CE marks go unnoticed but nothing else breaks
+    let _ = sockopt::recv_tos(&sock);
+    // PLPMTUD probes only tell something if routers drop them instead of fragmenting them
+    let _ = sockopt::set_df(&sock, true);
+    Ok(sock)
+}
+
+/// Access to the ECN field and the DF bit of the IP header through socket options
+#[cfg(target_os = "linux")]
+mod sockopt {
+    use super::ECT0;
+    use std::io;
+    use std::mem;
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+    use std::os::unix::io::AsRawFd;
+    use tokio::net::UdpSocket;
+
+    fn set_option(
+        sock: &UdpSocket,
+        level: libc::c_int,
+        name: libc::c_int,
+        value: libc::c_int,
+    ) -> io::Result<()> {
+        let result = unsafe {
+            libc::setsockopt(
+                sock.as_raw_fd(),
+                level,
+                name,
+                &value as *const libc::c_int as *const libc::c_void,
+                mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    fn is_ipv6(sock: &UdpSocket) -> io::Result<bool> {
+        Ok(sock.local_addr()?.is_ipv6())
+    }
+
+    /// Sets the ECT(0) codepoint on the datagrams sent from the socket
+    pub fn set_ect(sock: &UdpSocket, ect: bool) -> io::Result<()> {
+        let value = if ect { ECT0 as libc::c_int } else { 0 };
+        if is_ipv6(sock)? {
+            set_option(sock, libc::IPPROTO_IPV6, libc::IPV6_TCLASS, value)
+        } else {
+            set_option(sock, libc::IPPROTO_IP, libc::IP_TOS, value)
+        }
+    }
+
+    /// Sets the DF bit on the datagrams sent from the socket if `df` is true.
+    /// The PMTU the OS learnt from ICMP is ignored either way.
+    pub fn set_df(sock: &UdpSocket, df: bool) -> io::Result<()> {
+        if is_ipv6(sock)? {
+            let value = if df {
+                libc::IPV6_PMTUDISC_PROBE
+            } else {
+                libc::IPV6_PMTUDISC_DONT
+            };
+            set_option(sock, libc::IPPROTO_IPV6, libc::IPV6_MTU_DISCOVER, value)
+        } else {
+            let value = if df {
+                libc::IP_PMTUDISC_PROBE
+            } else {
+                libc::IP_PMTUDISC_DONT
+            };
+            set_option(sock, libc::IPPROTO_IP, libc::IP_MTU_DISCOVER, value)
+        }
+    }
+
+    /// Asks for the TOS/traffic class of every datagram recvd on the socket
+    pub fn recv_tos(sock: &UdpSocket) -> io::Result<()> {
+        if is_ipv6(sock)? {
+            set_option(sock, libc::IPPROTO_IPV6, libc::IPV6_RECVTCLASS, 1)
+        } else {
+            set_option(sock, libc::IPPROTO_IP, libc::IP_RECVTOS, 1)
+        }
+    }
+
+    /// Recvs a datagram along with the TOS/traffic class it arrived with
+    pub fn recv_from(sock: &UdpSocket, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, u8)> {
+        let mut addr: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        let mut iov = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        };
+        // u64s keep the control msgs aligned
+        let mut control = [0u64; 8];
+        let mut msg: libc::msghdr = unsafe { mem::zeroed() };
+        msg.msg_name = &mut addr as *mut libc::sockaddr_storage as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        msg.msg_iov = &mut iov;
+        msg.msg_iovlen = 1;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+
+        let len = unsafe { libc::recvmsg(sock.as_raw_fd(), &mut msg, 0) };
+        if len < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut tos = 0;
+        let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&msg) };
+        while !cmsg.is_null() {
+            let (level, kind) = unsafe { ((*cmsg).cmsg_level, (*cmsg).cmsg_type) };
+            let data = unsafe { libc::CMSG_DATA(cmsg) };
+            if level == libc::IPPROTO_IP && kind == libc::IP_TOS {
+                tos = unsafe { *data };
+            } else if level == libc::IPPROTO_IPV6 && kind == libc::IPV6_TCLASS {
+                tos = unsafe { std::ptr::read_unaligned(data as *const libc::c_int) } as u8;
+            }
+            cmsg = unsafe { libc::CMSG_NXTHDR(&msg, cmsg) };
+        }
+
+        Ok((len as usize, to_socket_addr(&addr)?, tos))
+    }
+
+    fn to_socket_addr(addr: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+        match addr.ss_family as libc::c_int {
+            libc::AF_INET => {
+                let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in) };
+                Ok(SocketAddr::V4(SocketAddrV4::new(
+                    Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr)),
+                    u16::from_be(addr.sin_port),
+                )))
+            }
+            libc::AF_INET6 => {
+                let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in6) };
+                Ok(SocketAddr::V6(SocketAddrV6::new(
+                    Ipv6Addr::from(addr.sin6_addr.s6_addr),
+                    u16::from_be(addr.sin6_port),
+                    addr.sin6_flowinfo,
+                    addr.sin6_scope_id,
+                )))
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "unknown address family",
+            )),
+        }
+    }
+}
+
+/// ECN isn't available on this platform so datagrams are recvd without their TOS.
+/// The DF bit is left to the OS.
+#[cfg(not(target_os = "linux"))]
+mod sockopt {
+    use std::io;
+    use std::net::SocketAddr;
+    use tokio::net::UdpSocket;
+
+    pub fn set_ect(_sock: &UdpSocket, ect: bool) -> io::Result<()> {
+        if ect {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "ECN unavailable",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn set_df(_sock: &UdpSocket, _df: bool) -> io::Result<()> {
+        Ok(())
+    }
+
+    pub fn recv_tos(_sock: &UdpSocket) -> io::Result<()> {
+        Ok(())
+    }
 
-        Ok((buf[..len].to_vec(), addr))
+    pub fn recv_from(sock: &UdpSocket, buf: &mut [u8]) -> io::Result<(usize, SocketAddr, u8)> {
+        let (len, addr) = sock.try_recv_from(buf)?;
+        Ok((len, addr, 0))
     }
 }
@@ -0,0 +1,12 @@
+//! TSNs are compared using serial number arithmetic (RFC 1982)
+//! since they wrap around after 2^32 - 1.
+
+/// Returns true if `a` comes before `b`
+pub fn tsn_lt(a: u32, b: u32) -> bool {
+    a != b && b.wrapping_sub(a) < (1 << 31)
+}
+
+/// Returns true if `a` comes before or is the same as `b`
+pub fn tsn_lte(a: u32, b: u32) -> bool {
+    a == b || tsn_lt(a, b)
+}
@@ -0,0 +1,76 @@
+use hyades_core::asconf::{AsconfReceiver, AsconfSender, AsconfVerdict};
+use hyades_core::chunk::{AsconfAck, AsconfOp, AsconfResponse, DELETE_LAST_ADDR_CAUSE};
+use std::net::IpAddr;
+
+fn ip(addr: &str) -> IpAddr {
+    addr.parse().unwrap()
+}
+
+#[test]
+fn test_one_asconf_outstanding_at_a_time() {
+    let mut sender = AsconfSender::new(100);
+
+    let asconf = sender
+        .send(ip("10.0.0.1"), &[(AsconfOp::AddIp, ip("10.0.0.2"))])
+        .unwrap();
+    assert!(asconf.serial_no() == 100);
+    assert!(sender
+        .send(ip("10.0.0.1"), &[(AsconfOp::SetPrimary, ip("10.0.0.2"))])
+        .is_none());
+
+    // an ack for another serial no is ignored
+    assert!(!sender.on_ack(&AsconfAck::new(99, vec![])));
+    assert!(sender.outstanding().is_some());
+
+    // requests without a response succeeded
+    assert!(sender.on_ack(&AsconfAck::new(100, vec![])));
+    let results = sender.take_results().unwrap();
+    assert!(results.len() == 1 && results[0].1.is_none());
+
+    let asconf = sender
+        .send(ip("10.0.0.1"), &[(AsconfOp::DeleteIp, ip("10.0.0.2"))])
+        .unwrap();
+    assert!(asconf.serial_no() == 101);
+}
+
+#[test]
+fn test_asconf_errors_are_reported() {
+    let mut sender = AsconfSender::new(0);
+    let asconf = sender
+        .send(ip("10.0.0.1"), &[(AsconfOp::DeleteIp, ip("10.0.0.1"))])
+        .unwrap();
+    let response = AsconfResponse {
+        correlation_id: asconf.requests()[0].correlation_id,
+        error: Some(DELETE_LAST_ADDR_CAUSE),
+    };
+
+    assert!(sender.on_ack(&AsconfAck::new(0, vec![response])));
+    let results = sender.take_results().unwrap();
+    assert!(results[0].1 == Some(DELETE_LAST_ADDR_CAUSE));
+}
+
+#[test]
+fn test_peer_asconfs_are_processed_in_sequence() {
+    let mut sender = AsconfSender::new(7);
+    let mut receiver = AsconfReceiver::new(7);
+    let first = sender
+        .send(ip("10.0.0.1"), &[(AsconfOp::AddIp, ip("10.0.0.2"))])
+        .unwrap();
+
+    assert!(matches!(receiver.on_asconf(&first), AsconfVerdict::New));
+    receiver.on_processed(AsconfAck::new(first.serial_no(), vec![]));
+
+    // the ack got lost so the peer retransmits
+    assert!(matches!(
+        receiver.on_asconf(&first),
+        AsconfVerdict::Retransmitted(ack) if ack.serial_no() == 7
+    ));
+
+    sender.on_ack(&AsconfAck::new(7, vec![]));
+    let _ = sender.send(ip("10.0.0.1"), &[(AsconfOp::AddIp, ip("10.0.0.3"))]);
+    sender.on_ack(&AsconfAck::new(8, vec![]));
+    let third = sender
+        .send(ip("10.0.0.1"), &[(AsconfOp::AddIp, ip("10.0.0.4"))])
+        .unwrap();
+    assert!(matches!(receiver.on_asconf(&third), AsconfVerdict::Stale));
+}
@@ -0,0 +1,30 @@
+use hyades_core::config::AssociationConfig;
+use hyades_core::endpoint::SCTPEndpoint;
+use std::net::{SocketAddr, UdpSocket};
+
+/// Returns a local addr with a port the OS picked as free
+fn free_addr() -> SocketAddr {
+    UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_both_ends_interleave_once_i_data_is_negotiated() {
+    let config = AssociationConfig {
+        interleaving_enabled: true,
+        ..AssociationConfig::default()
+    };
+    let recvr_addr = free_addr().to_string();
+    let sender_addr = free_addr().to_string();
+
+    // the recvr is polled first so it is bound before the sender's INIT goes out.
+    // both ends are waited on until their handshake is done
+    let (recvr, sender) = tokio::join!(
+        SCTPEndpoint::associate_recv_with_config(&recvr_addr, config.clone()),
+        SCTPEndpoint::associate_send_with_config(&sender_addr, &recvr_addr, config),
+    );
+    assert!(sender.unwrap().interleaving());
+    assert!(recvr.unwrap().interleaving());
+}
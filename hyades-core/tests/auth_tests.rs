@@ -0,0 +1,97 @@
+use hyades_core::auth::{key_vector, Authenticator};
+use hyades_core::chunk::{Auth, Data, ParamType, Parameter, Sack};
+use hyades_core::packet::Packet;
+use std::convert::TryFrom;
+
+/// Returns two authenticators that negotiated AUTH with each other
+fn negotiated() -> (Authenticator, Authenticator) {
+    let mut a = Authenticator::new(&[0, 3]);
+    let mut b = Authenticator::new(&[0x80, 0xc1]);
+    a.on_peer_params(&b.params());
+    b.on_peer_params(&a.params());
+    (a, b)
+}
+
+#[test]
+fn test_sign_and_verify() {
+    let (mut a, mut b) = negotiated();
+    assert!(a.is_negotiated() && b.is_negotiated());
+    assert!(a.requires_auth(0) && !a.requires_auth(0xc1));
+    assert!(b.peer_requires_auth(3) && a.peer_requires_auth(0xc1));
+
+    let covered = b"some chunks".to_vec();
+    let auth = a.sign(&covered);
+    assert!(auth.hmac().len() == 32);
+    assert!(b.verify(&auth, &covered));
+    assert!(!b.verify(&auth, b"other chunks"));
+
+    // both ends must use the same key
+    a.add_key(1, b"secret".to_vec());
+    a.set_active_key(1).unwrap();
+    let auth = a.sign(&covered);
+    assert!(!b.verify(&auth, &covered));
+    b.add_key(1, b"secret".to_vec());
+    assert!(b.verify(&auth, &covered));
+
+    // an HMAC cut short or too long for its HMAC id
+    for len in [0, 20, 31, 33] {
+        let mut hmac = auth.hmac().to_vec();
+        hmac.resize(len, 0);
+        let auth = Auth::new(auth.key_id(), auth.hmac_id(), hmac);
+        assert!(!b.verify(&auth, &covered));
+    }
+}
+
+#[test]
+fn test_key_management() {
+    let (mut a, _) = negotiated();
+    assert!(a.set_active_key(2).is_err());
+    a.add_key(2, b"key".to_vec());
+    a.set_active_key(2).unwrap();
+    // the active key can't be deleted
+    assert!(a.delete_key(2).is_err());
+    a.delete_key(0).unwrap();
+    assert!(a.delete_key(0).is_err());
+
+    // nothing verifies without negotiating AUTH
+    let unnegotiated = Authenticator::new(&[0]);
+    assert!(!unnegotiated.is_negotiated() && !unnegotiated.requires_auth(0));
+    assert!(!unnegotiated.verify(&a.sign(b"chunks"), b"chunks"));
+}
+
+#[test]
+fn test_authenticated_packet() {
+    let (a, b) = negotiated();
+    let mut packet = Packet::new(5000, 5001, 1);
+    packet.add_chunk(Box::new(Sack::new(1, 1000, 0, 0, None, None)));
+    packet.add_chunk(Box::new(Data::new(
+        1,
+        0,
+        0,
+        0,
+        true,
+        true,
+        b"hello".to_vec(),
+    )));
+
+    let bytes = packet.to_authenticated_bytes(1, |covered| a.sign(covered));
+    let packet = Packet::try_from(bytes).unwrap();
+    assert!(packet.chunks.len() == 3);
+    let auth = Auth::from(&packet.chunks[1].get_bytes()[..]);
+    assert!(b.verify(&auth, packet.bytes_after(1)));
+    assert!(!b.verify(&auth, packet.bytes_after(0)));
+}
+
+#[test]
+fn test_key_vector() {
+    let random = Parameter::new(ParamType::Random, (1..=32).collect());
+    // 2 chunk types, so the param is followed by 2 bytes of padding when sent
+    let chunks = Parameter::new(ParamType::ChunkList, vec![0x80, 0xc1]);
+    let hmacs = Parameter::new(ParamType::HmacAlgo, vec![0, 3, 0, 1]);
+
+    let mut expected = vec![0x80, 0x02, 0, 36];
+    expected.extend(1..=32u8);
+    expected.extend([0x80, 0x03, 0, 6, 0x80, 0xc1]);
+    expected.extend([0x80, 0x04, 0, 8, 0, 3, 0, 1]);
+    assert!(key_vector(&[&random, &chunks, &hmacs]) == expected);
+}
@@ -1,4 +1,10 @@
-use hyades_core::chunk::{Data, Init, ParamType};
+use hyades_core::chunk::{
+    Asconf, AsconfAck, AsconfOp, AsconfRequest, AsconfResponse, Chunk, Cwr, Data, Ecne, Error,
+    ForwardTsn, IData, IForwardTsn, Init, InvalidStreamId, NrSack, Pad, ParamType, Parameter,
+    ReConfig, ReconfigRequest, ReconfigResponse, ReconfigResult, SkippedMessages,
+    UnrecognizedChunkType, DELETE_SOURCE_ADDR_CAUSE, EDMID_DTLS,
+};
+use std::net::IpAddr;
 
 #[test]
 fn test_init_conversion() {
@@ -57,3 +63,272 @@ fn test_data_chunk() {
     assert!(chunk.data.len() == 4);
     assert!(chunk.data == vec![1, 2, 3, 4]);
 }
+
+#[test]
+fn test_error_chunk_with_invalid_stream_id() {
+    let error = Error::new(vec![Box::new(InvalidStreamId::new(7))]);
+    let bytes = error.get_bytes();
+    assert!(bytes == vec![9u8, 0, 0, 12, 0, 1, 0, 8, 0, 7, 0, 0]);
+
+    let error = Error::from(&bytes[..]);
+    assert!(error.errors().len() == 1);
+    assert!(error.get_bytes() == bytes);
+}
+
+#[test]
+fn test_error_chunk_with_unrecognized_chunk_type() {
+    let cause = UnrecognizedChunkType::new(vec![0xf0, 0, 0, 5, 1]);
+    let error = Error::new(vec![Box::new(cause)]);
+    let bytes = error.get_bytes();
+    // the cause is padded to 4 bytes
+    assert!(bytes == vec![9u8, 0, 0, 16, 0, 6, 0, 9, 0xf0, 0, 0, 5, 1, 0, 0, 0]);
+
+    let error = Error::from(&bytes[..]);
+    assert!(error.errors().len() == 1);
+    assert!(error.get_bytes() == bytes);
+}
+
+#[test]
+fn test_error_chunk_with_unparsed_causes() {
+    // an unresolvable addr cause followed by a cause of an unknown code,
+    // both kept as they are so the chunk encodes to the same bytes
+    let bytes = vec![
+        9u8, 0, 0, 24, 0, 5, 0, 9, 0, 5, 0, 5, 1, 0, 0, 0, 0x7f, 0, 0, 6, 1, 2, 0, 0,
+    ];
+    let error = Error::from(&bytes[..]);
+    assert!(error.errors().len() == 2);
+    assert!(error.get_bytes() == bytes);
+    assert!(Error::from(&error.get_bytes()[..]).errors().len() == 2);
+
+    // a chunk length past the end of the buffer
+    let error = Error::from(&[9u8, 0, 0, 40, 0, 1, 0, 8, 0, 7, 0, 0][..]);
+    assert!(error.errors().len() == 1);
+}
+
+#[test]
+fn test_pad_chunk() {
+    let pad = Pad::new(8);
+    let bytes = pad.get_bytes();
+    assert!(bytes == vec![0x84u8, 0, 0, 12, 0, 0, 0, 0, 0, 0, 0, 0]);
+    assert!(Pad::from(&bytes[..]).padding_len() == 8);
+}
+
+#[test]
+fn test_unordered_data_chunk() {
+    let chunk = Data::new(1, 0, 0, 0, true, true, vec![1]).unordered(true);
+    let bytes = chunk.get_bytes();
+    assert!(bytes[1] == 0x07);
+
+    let chunk = Data::from(bytes);
+    assert!(chunk.is_unordered() && chunk.is_beginning() && chunk.is_end());
+}
+
+#[test]
+fn test_init_with_address_params() {
+    let v4: IpAddr = "10.0.0.1".parse().unwrap();
+    let v6: IpAddr = "fe80::1".parse().unwrap();
+    let chunk = Init::new(
+        1,
+        1000,
+        1,
+        1,
+        Some(vec![Parameter::from_ip(v4), Parameter::from_ip(v6)]),
+    );
+    let chunk = Init::from(chunk.get_bytes());
+    let params = chunk.optional_params.unwrap();
+    assert!(params[0].param_type == ParamType::Ipv4Addr);
+    assert!(params[1].param_type == ParamType::Ipv6Addr);
+    let ips: Vec<_> = params.iter().filter_map(|param| param.ip()).collect();
+    assert_eq!(ips, vec![v4, v6]);
+}
+
+#[test]
+fn test_zero_checksum_acceptable_param() {
+    let chunk = Init::new(
+        1,
+        1000,
+        1,
+        1,
+        Some(vec![Parameter::zero_checksum_acceptable(EDMID_DTLS)]),
+    );
+    let bytes = chunk.get_bytes();
+    assert!(bytes[20..22] == [0x80, 0x01] && bytes[24..28] == [0, 0, 0, 1]);
+
+    let chunk = Init::from(bytes);
+    let params = chunk.optional_params.unwrap();
+    assert!(params[0].param_type == ParamType::ZeroChecksumAcceptable);
+    assert!(params[0].edmid() == Some(EDMID_DTLS));
+    assert!(Parameter::from_ip("10.0.0.1".parse().unwrap())
+        .edmid()
+        .is_none());
+}
+
+#[test]
+fn test_adaptation_layer_indication_param() {
+    let chunk = Init::new(
+        1,
+        1000,
+        1,
+        1,
+        Some(vec![Parameter::adaptation_layer_indication(0x01020304)]),
+    );
+    let bytes = chunk.get_bytes();
+    assert!(bytes[20..22] == [0xc0, 0x06] && bytes[24..28] == [1, 2, 3, 4]);
+
+    let chunk = Init::from(bytes);
+    let params = chunk.optional_params.unwrap();
+    assert!(params[0].param_type == ParamType::AdaptationLayerIndication);
+    assert!(params[0].adaptation_code_point() == Some(0x01020304));
+    assert!(params[0].edmid().is_none());
+}
+
+#[test]
+fn test_asconf_and_asconf_ack() {
+    let request = AsconfRequest {
+        op: AsconfOp::AddIp,
+        correlation_id: 1,
+        addr: "fe80::2".parse().unwrap(),
+    };
+    let chunk = Asconf::new(42, "10.0.0.1".parse().unwrap(), vec![request.clone()]);
+    let chunk = Asconf::from(&chunk.get_bytes()[..]);
+    assert!(chunk.serial_no() == 42);
+    assert!(chunk.addr() == Some("10.0.0.1".parse().unwrap()));
+    assert!(chunk.requests() == [request]);
+
+    let responses = vec![
+        AsconfResponse {
+            correlation_id: 1,
+            error: None,
+        },
+        AsconfResponse {
+            correlation_id: 2,
+            error: Some(DELETE_SOURCE_ADDR_CAUSE),
+        },
+    ];
+    let chunk = AsconfAck::new(42, responses.clone());
+    let chunk = AsconfAck::from(&chunk.get_bytes()[..]);
+    assert!(chunk.serial_no() == 42);
+    assert!(chunk.responses() == &responses[..]);
+}
+
+#[test]
+fn test_asconf_with_truncated_params() {
+    // the address param claims more bytes than the chunk has
+    let bytes = [0xc1, 0, 0, 16, 0, 0, 0, 42, 0, 5, 0, 8, 10, 0, 0, 1];
+    let chunk = Asconf::from(&bytes[..]);
+    assert!(chunk.serial_no() == 42);
+    assert!(chunk.addr().is_none());
+    assert!(chunk.requests().is_empty());
+
+    // a param header cut short
+    let bytes = [0x80, 0, 0, 10, 0, 0, 0, 42, 0, 5];
+    assert!(AsconfAck::from(&bytes[..]).responses().is_empty());
+}
+
+#[test]
+fn test_forward_tsn_chunk() {
+    let fwd = ForwardTsn::new(1000, vec![(0, 5), (3, 65535)]);
+    let bytes = fwd.get_bytes();
+    assert!(bytes.len() == 16);
+    assert!(bytes[0] == 0xc0);
+
+    let fwd = ForwardTsn::from(&bytes[..]);
+    assert!(fwd.new_cumulative_tsn() == 1000);
+    assert!(fwd.streams() == [(0, 5), (3, 65535)]);
+
+    // a stream/seq no pair cut short
+    let fwd = ForwardTsn::from(&[0xc0, 0, 0, 14, 0, 0, 3, 232, 0, 1, 0, 2, 0, 3][..]);
+    assert!(fwd.new_cumulative_tsn() == 1000);
+    assert!(fwd.streams() == [(1, 2)]);
+}
+
+#[test]
+fn test_reconfig_chunk() {
+    let requests = vec![
+        ReconfigRequest::OutgoingSsnReset {
+            seq_no: 7,
+            response_seq_no: 3,
+            last_tsn: 100,
+            streams: vec![1, 2, 5],
+        },
+        ReconfigRequest::AddIncomingStreams {
+            seq_no: 8,
+            num_streams: 4,
+        },
+    ];
+    let responses = vec![ReconfigResponse {
+        seq_no: 4,
+        result: ReconfigResult::SuccessPerformed,
+        next_tsns: Some((1 << 31, 10)),
+    }];
+    let reconfig = ReConfig::new(responses.clone(), requests.clone());
+    let bytes = reconfig.get_bytes();
+    // header + response with next TSNs + outgoing reset of 3 streams (padded) + add streams
+    assert!(bytes.len() == 4 + 20 + 24 + 12);
+    assert!(bytes[0] == 0x82);
+    assert!(u16::from_be_bytes([bytes[2], bytes[3]]) as usize == bytes.len());
+
+    let reconfig = ReConfig::from(&bytes[..]);
+    assert!(reconfig.responses() == responses);
+    assert!(reconfig.requests() == requests);
+}
+
+#[test]
+fn test_i_data_chunk() {
+    let first = IData::new(7, 3, 70000, 0, 51, true, false, b"hello".to_vec());
+    let bytes = first.get_bytes();
+    assert!(bytes[0] == 0x40);
+    assert!(u16::from_be_bytes([bytes[2], bytes[3]]) == 25);
+    assert!(bytes.len() == 28);
+
+    let first = IData::from(&bytes[..]);
+    assert!(first.tsn() == 7 && first.stream_id() == 3 && first.message_id() == 70000);
+    assert!(first.fragment_seq_no() == 0 && first.payload_proto_id() == Some(51));
+    assert!(first.payload() == b"hello");
+
+    // fragments after the first one carry their fragment seq no instead of the ppid
+    let last = IData::new(8, 3, 70000, 1, 51, false, true, b"!".to_vec()).unordered(true);
+    let last = IData::from(&last.get_bytes()[..]);
+    assert!(last.fragment_seq_no() == 1 && last.payload_proto_id().is_none());
+    assert!(last.is_end() && last.is_unordered() && !last.is_beginning());
+}
+
+#[test]
+fn test_i_forward_tsn_chunk() {
+    let streams = vec![SkippedMessages {
+        stream_id: 2,
+        unordered: true,
+        message_id: 9,
+    }];
+    let bytes = IForwardTsn::new(100, streams.clone()).get_bytes();
+    assert!(bytes.len() == 16 && bytes[0] == 0xc2);
+
+    let fwd = IForwardTsn::from(&bytes[..]);
+    assert!(fwd.new_cumulative_tsn() == 100);
+    assert!(fwd.streams() == streams);
+}
+
+#[test]
+fn test_ecne_and_cwr_chunks() {
+    let bytes = Ecne::new(7).get_bytes();
+    assert!(bytes == [12, 0, 0, 8, 0, 0, 0, 7]);
+    assert!(Ecne::from(&bytes[..]).lowest_tsn() == 7);
+
+    let bytes = Cwr::new(9).get_bytes();
+    assert!(bytes == [13, 0, 0, 8, 0, 0, 0, 9]);
+    assert!(Cwr::from(&bytes[..]).lowest_tsn() == 9);
+}
+
+#[test]
+fn test_nr_sack_chunk() {
+    let sack = NrSack::new(100, 5000, vec![(2, 3)], vec![(5, 5), (7, 9)], vec![101]);
+    let bytes = sack.get_bytes();
+    assert!(bytes.len() == 36 && bytes[0] == 0x10);
+    assert!(u16::from_be_bytes([bytes[2], bytes[3]]) == 36);
+
+    let sack = NrSack::from(&bytes[..]);
+    assert!(sack.cumulative_tsn_ack() == 100 && sack.a_rwnd() == 5000);
+    assert!(sack.gap_ack_blocks() == [(2, 3)]);
+    assert!(sack.nr_gap_ack_blocks() == [(5, 5), (7, 9)]);
+    assert!(sack.dup_tsns() == [101]);
+}
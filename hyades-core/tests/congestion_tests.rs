@@ -0,0 +1,70 @@
+use hyades_core::congestion::{Bbr, BbrState, CongestionAlgorithm, CongestionControl, Standard};
+use std::time::Duration;
+
+#[test]
+fn test_standard_initial_cwnd() {
+    let cc = Standard::new(1500);
+    assert!(cc.cwnd() == 4380);
+
+    let cc = Standard::new(500);
+    assert!(cc.cwnd() == 2000);
+}
+
+#[test]
+fn test_standard_slow_start_and_timeout() {
+    let mut cc = Standard::new(1500);
+    let cwnd = cc.cwnd();
+
+    // cwnd isn't grown when it isn't fully utilized
+    cc.on_ack(1000, 1000, None);
+    assert!(cc.cwnd() == cwnd);
+
+    cc.on_ack(3000, cwnd, None);
+    assert!(cc.cwnd() == cwnd + 1500);
+
+    cc.on_timeout();
+    assert!(cc.cwnd() == 1500);
+    assert!(cc.ssthresh() == 6000);
+}
+
+#[test]
+fn test_cubic_loss_reduces_by_beta() {
+    let mut cc = CongestionAlgorithm::Cubic.build(1000);
+    for _ in 0..20 {
+        let cwnd = cc.cwnd();
+        cc.on_ack(1000, cwnd, Some(Duration::from_millis(50)));
+    }
+    let cwnd = cc.cwnd();
+
+    cc.on_loss(cwnd);
+    assert!(cc.cwnd() == (cwnd as f64 * 0.7) as u32);
+}
+
+#[test]
+fn test_bbr_sizes_cwnd_from_bdp() {
+    let mut cc = Bbr::new(1000);
+    // 100_000 bytes acked every 100ms => 1MB/s, bdp = 100KB
+    for _ in 0..10 {
+        cc.on_ack(100_000, 100_000, Some(Duration::from_millis(100)));
+    }
+    assert!(cc.state() != BbrState::Startup);
+    assert!(cc.cwnd() >= 100_000);
+
+    cc.on_timeout();
+    assert!(cc.cwnd() == 1000);
+}
+
+#[test]
+fn test_bbr_counts_rounds_not_acks() {
+    let mut cc = Bbr::new(1000);
+    // a round trip takes 10 acks of 10_000 bytes to deliver the 100_000 in flight
+    for _ in 0..20 {
+        cc.on_ack(10_000, 100_000, Some(Duration::from_millis(100)));
+    }
+    assert!(cc.state() == BbrState::Startup);
+
+    for _ in 0..10 {
+        cc.on_ack(10_000, 100_000, Some(Duration::from_millis(100)));
+    }
+    assert!(cc.state() != BbrState::Startup);
+}
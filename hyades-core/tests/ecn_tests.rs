@@ -0,0 +1,32 @@
+use hyades_core::chunk::{Cwr, Ecne};
+use hyades_core::ecn::{EcnReceiver, EcnSender};
+
+#[test]
+fn test_cwnd_is_reduced_once_per_window() {
+    let mut sender = EcnSender::new();
+
+    let (cwr, reduce) = sender.on_ecne(&Ecne::new(10), 20);
+    assert!(reduce && cwr.lowest_tsn() == 20);
+    // marks on DATA sent before the reduction only get a CWR
+    let (cwr, reduce) = sender.on_ecne(&Ecne::new(15), 25);
+    assert!(!reduce && cwr.lowest_tsn() == 20);
+    let (cwr, reduce) = sender.on_ecne(&Ecne::new(21), 30);
+    assert!(reduce && cwr.lowest_tsn() == 30);
+}
+
+#[test]
+fn test_ce_marks_are_echoed_until_cwr() {
+    let mut receiver = EcnReceiver::new();
+    assert!(receiver.ecne().is_none());
+
+    receiver.on_ce(5);
+    receiver.on_ce(8);
+    receiver.on_ce(6);
+    assert!(receiver.ecne().unwrap().lowest_tsn() == 8);
+
+    // a CWR that doesn't cover the last mark keeps the echo going
+    receiver.on_cwr(&Cwr::new(7));
+    assert!(receiver.ecne().is_some());
+    receiver.on_cwr(&Cwr::new(8));
+    assert!(receiver.ecne().is_none());
+}
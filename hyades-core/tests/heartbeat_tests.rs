@@ -0,0 +1,34 @@
+use hyades_core::chunk::{Chunk, Heartbeat, HeartbeatAck};
+use hyades_core::heartbeat::{HeartbeatInfo, HeartbeatTimer};
+use std::convert::TryFrom;
+use std::time::Duration;
+
+#[test]
+fn test_heartbeat_info_is_echoed() {
+    let info = HeartbeatInfo {
+        timestamp: 1234,
+        nonce: 0xdeadbeef,
+    };
+    let heartbeat = Heartbeat::new((&info).into());
+    let bytes = heartbeat.get_bytes();
+    assert!(bytes.len() == 24);
+
+    let heartbeat = Heartbeat::from(&bytes[..]);
+    let ack = HeartbeatAck::from(&HeartbeatAck::new(heartbeat.info().to_vec()).get_bytes()[..]);
+    assert!(HeartbeatInfo::try_from(ack.info()) == Ok(info));
+}
+
+#[test]
+fn test_heartbeat_ack_must_match_nonce() {
+    let mut timer = HeartbeatTimer::new(Duration::from_secs(30));
+    let info = timer.on_send(1);
+    assert!(timer.is_pending());
+
+    let stale = HeartbeatInfo { nonce: 2, ..info };
+    assert!(timer.on_ack(&stale).is_none());
+    assert!(timer.on_ack(&info).is_some());
+    assert!(!timer.is_pending());
+
+    timer.set(false, Duration::from_secs(30));
+    assert!(timer.deadline().is_none());
+}
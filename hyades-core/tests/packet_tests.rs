@@ -0,0 +1,166 @@
+use hyades_core::chunk::{ChunkType, CookieEcho, Data, Pad, Sack};
+use hyades_core::cookie::Cookie;
+use hyades_core::packet::Packet;
+use std::convert::TryFrom;
+
+#[test]
+fn test_packet_roundtrip() {
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(Data::new(7, 0, 0, 0, true, true, vec![1, 2, 3])));
+    packet.add_chunk(Box::new(Sack::new(6, 1500, 0, 0, None, None)));
+
+    let packet = Packet::try_from(Vec::<u8>::from(&packet)).unwrap();
+    assert!(packet.chunks.len() == 2);
+
+    let data = Data::from(packet.find_chunk(ChunkType::Data).unwrap().get_bytes());
+    assert!(data.tsn() == 7);
+    assert!(data.payload_len() == 3);
+
+    let sack = Sack::from(packet.find_chunk(ChunkType::Sack).unwrap().get_bytes());
+    assert!(sack.cumulative_tsn_ack == 6);
+    assert!(sack.a_rwnd == 1500);
+}
+
+#[test]
+fn test_packet_with_bad_checksum() {
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(Sack::new(6, 1500, 0, 0, None, None)));
+
+    let mut bytes = Vec::<u8>::from(&packet);
+    bytes[8] ^= 0xff;
+    assert!(Packet::try_from(bytes).is_err());
+}
+
+#[test]
+fn test_zero_checksum() {
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(Sack::new(6, 1500, 0, 0, None, None)));
+    packet.set_zero_checksum(true);
+
+    let bytes = Vec::<u8>::from(&packet);
+    assert!(bytes[8..12] == [0; 4]);
+    // zero checksums are only accepted once negotiated
+    assert!(Packet::try_from(bytes.clone()).is_err());
+    assert!(Packet::parse(bytes.clone(), false).is_err());
+    assert!(Packet::parse(bytes, true).unwrap().chunks.len() == 1);
+
+    // correct checksums are still accepted, bad ones aren't
+    packet.set_zero_checksum(false);
+    let mut bytes = Vec::<u8>::from(&packet);
+    assert!(Packet::parse(bytes.clone(), true).is_ok());
+    bytes[8] ^= 0xff;
+    assert!(Packet::parse(bytes, true).is_err());
+}
+
+#[test]
+fn test_zero_checksum_not_used_for_cookie_echo() {
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(CookieEcho::new(Cookie::new())));
+    packet.set_zero_checksum(true);
+
+    let bytes = Vec::<u8>::from(&packet);
+    assert!(bytes[8..12] != [0; 4]);
+    assert!(Packet::try_from(bytes).is_ok());
+}
+
+// appends a raw chunk to an encoded packet and fixes up the checksum
+fn with_chunk(mut bytes: Vec<u8>, chunk: &[u8]) -> Vec<u8> {
+    bytes.extend(chunk);
+    bytes[8..12].copy_from_slice(&[0; 4]);
+    let checksum = crc32c::crc32c(&bytes);
+    bytes[8..12].copy_from_slice(&checksum.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn test_pad_to() {
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(Sack::new(6, 1500, 0, 0, None, None)));
+    packet.pad_to(101);
+
+    let bytes = Vec::<u8>::from(&packet);
+    assert!(bytes.len() == 104);
+    let packet = Packet::try_from(bytes).unwrap();
+    let pad = Pad::from(&packet.find_chunk(ChunkType::Pad).unwrap().get_bytes()[..]);
+    assert!(pad.padding_len() == 104 - 12 - 16 - 4);
+
+    // a packet that is long enough already isn't padded
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(Sack::new(6, 1500, 0, 0, None, None)));
+    packet.pad_to(20);
+    assert!(Vec::<u8>::from(&packet).len() == 28);
+}
+
+#[test]
+fn test_unknown_chunks() {
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(Sack::new(6, 1500, 0, 0, None, None)));
+    let bytes = Vec::<u8>::from(&packet);
+
+    // skipped
+    let packet = Packet::try_from(with_chunk(bytes.clone(), &[0xb0, 0, 0, 5, 1, 0, 0, 0])).unwrap();
+    assert!(packet.chunks.len() == 1);
+    assert!(packet.unrecognized.is_empty());
+
+    // skipped and reported
+    let packet = Packet::try_from(with_chunk(bytes.clone(), &[0xf0, 0, 0, 4])).unwrap();
+    assert!(packet.chunks.len() == 1);
+    assert!(packet.unrecognized == [vec![0xf0, 0, 0, 4]]);
+
+    // the chunks after it are discarded and the chunk reported
+    let bytes = with_chunk(bytes, &[0x70, 0, 0, 4]);
+    let packet = Packet::try_from(with_chunk(bytes.clone(), &[0x84, 0, 0, 4])).unwrap();
+    assert!(packet.chunks.len() == 1);
+    assert!(packet.find_chunk(ChunkType::Sack).is_some());
+    assert!(packet.unrecognized == [vec![0x70, 0, 0, 4]]);
+
+    // the chunks after it are discarded
+    let mut packet = Packet::new(5000, 5001, 42);
+    packet.add_chunk(Box::new(Sack::new(6, 1500, 0, 0, None, None)));
+    let bytes = with_chunk(Vec::<u8>::from(&packet), &[0x30, 0, 0, 4]);
+    let packet = Packet::try_from(with_chunk(bytes, &[0x84, 0, 0, 4])).unwrap();
+    assert!(packet.chunks.len() == 1);
+    assert!(packet.unrecognized.is_empty());
+}
+
+#[test]
+fn test_truncated_interleaving_chunks() {
+    let bytes = Vec::<u8>::from(&Packet::new(5000, 5001, 42));
+    assert!(Packet::try_from(with_chunk(bytes.clone(), &[0x40, 0, 0, 8, 0, 0, 0, 1])).is_err());
+    assert!(Packet::try_from(with_chunk(bytes, &[0xc2, 0, 0, 4])).is_err());
+}
+
+#[test]
+fn test_truncated_asconf_chunks() {
+    let bytes = Vec::<u8>::from(&Packet::new(5000, 5001, 42));
+    assert!(Packet::try_from(with_chunk(bytes.clone(), &[0xc1, 0, 0, 4])).is_err());
+    assert!(Packet::try_from(with_chunk(bytes, &[0x80, 0, 0, 4])).is_err());
+}
+
+#[test]
+fn test_truncated_forward_tsn_chunk() {
+    let bytes = Vec::<u8>::from(&Packet::new(5000, 5001, 42));
+    assert!(Packet::try_from(with_chunk(bytes, &[0xc0, 0, 0, 4])).is_err());
+}
+
+#[test]
+fn test_truncated_auth_chunk() {
+    let bytes = Vec::<u8>::from(&Packet::new(5000, 5001, 42));
+    assert!(Packet::try_from(with_chunk(bytes.clone(), &[0x0f, 0, 0, 6, 0, 1, 0, 0])).is_err());
+    let packet = Packet::try_from(with_chunk(bytes, &[0x0f, 0, 0, 8, 0, 1, 0, 3])).unwrap();
+    assert!(packet.find_chunk(ChunkType::Auth).is_some());
+}
+
+#[test]
+fn test_short_chunks_dont_panic() {
+    let bytes = Vec::<u8>::from(&Packet::new(5000, 5001, 42));
+    for chunk_type in 0..=255u8 {
+        for len in [4u8, 8, 12, 16, 20, 24] {
+            for fill in [0u8, 0xff] {
+                let mut chunk = vec![chunk_type, 0, 0, len];
+                chunk.resize(len as usize, fill);
+                let _ = Packet::try_from(with_chunk(bytes.clone(), &chunk));
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use hyades_core::config::AssociationConfig;
+use hyades_core::path::{select_primary, Path, PathState};
+
+fn path(pf_max_retrans: u32, max_retrans: u32) -> Path {
+    let config = AssociationConfig {
+        path_pf_max_retrans: pf_max_retrans,
+        path_max_retrans: max_retrans,
+        ..AssociationConfig::default()
+    };
+    Path::new("127.0.0.1:5000".parse().unwrap(), &config)
+}
+
+#[test]
+fn test_path_goes_through_pf_to_inactive() {
+    let mut path = path(1, 3);
+
+    assert!(path.on_error().is_none());
+    assert!(path.on_error() == Some(PathState::PotentiallyFailed));
+    assert!(path.on_error().is_none());
+    assert!(path.on_error() == Some(PathState::Inactive));
+    assert!(path.on_error().is_none());
+    assert!(path.error_count() == 5);
+
+    assert!(path.on_ack() == Some(PathState::Active));
+    assert!(path.error_count() == 0);
+    assert!(path.on_ack().is_none());
+}
+
+#[test]
+fn test_pf_disabled() {
+    let mut path = path(2, 2);
+
+    assert!(path.on_error().is_none());
+    assert!(path.on_error().is_none());
+    assert!(path.on_error() == Some(PathState::Inactive));
+}
+
+#[test]
+fn test_failover_and_failback() {
+    let mut paths = vec![path(0, 1), path(0, 1), path(0, 1)];
+
+    // the primary fails so the next active path takes over
+    paths[0].on_error();
+    paths[0].on_error();
+    assert!(paths[0].state() == PathState::Inactive);
+    assert!(select_primary(&paths, 0, 0, true) == 1);
+
+    // the primary recovers
+    paths[0].on_ack();
+    assert!(select_primary(&paths, 1, 0, true) == 0);
+    assert!(select_primary(&paths, 1, 0, false) == 1);
+}
+
+#[test]
+fn test_potentially_failed_path_used_as_last_resort() {
+    let mut paths = vec![path(0, 1), path(0, 1)];
+
+    paths[0].on_error();
+    paths[0].on_error();
+    paths[1].on_error();
+    assert!(paths[1].state() == PathState::PotentiallyFailed);
+    assert!(select_primary(&paths, 0, 0, true) == 1);
+}
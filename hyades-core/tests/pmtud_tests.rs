@@ -0,0 +1,114 @@
+use hyades_core::pmtud::{Plpmtud, PlpmtudState, MAX_PROBES, PMTU_RAISE_TIMER};
+use std::time::Duration;
+use tokio::time::Instant;
+
+const RTO: Duration = Duration::from_secs(1);
+
+// runs the state machine over a path that drops packets larger than `path_mtu`
+// until it stops probing. returns the sizes probed
+fn probe(pmtud: &mut Plpmtud, path_mtu: u32, mut now: Instant) -> Vec<u32> {
+    let mut sizes = vec![];
+    let mut nonce = 0;
+    while let Some(size) = pmtud.on_timer(now, RTO, nonce) {
+        sizes.push(size);
+        if size <= path_mtu {
+            assert!(pmtud.on_ack(nonce, now));
+        } else {
+            now += RTO;
+        }
+        nonce += 1;
+    }
+    sizes
+}
+
+#[test]
+fn test_search_finds_the_pmtu() {
+    let mut pmtud = Plpmtud::new(1200, 1500, true);
+    let now = Instant::now();
+    assert!(pmtud.state() == PlpmtudState::Base);
+    assert!(pmtud.plpmtu() == 1200);
+
+    // the base is confirmed, then the largest size is tried first
+    let sizes = probe(&mut pmtud, 1500, now);
+    assert!(sizes == [1200, 1500]);
+    assert!(pmtud.state() == PlpmtudState::SearchComplete);
+    assert!(pmtud.plpmtu() == 1500);
+
+    let mut pmtud = Plpmtud::new(1200, 1500, true);
+    let sizes = probe(&mut pmtud, 1400, Instant::now());
+    assert!(sizes.iter().filter(|&&size| size == 1500).count() == MAX_PROBES as usize);
+    assert!(pmtud.state() == PlpmtudState::SearchComplete);
+    assert!(pmtud.plpmtu() <= 1400 && pmtud.plpmtu() > 1400 - 16);
+}
+
+#[test]
+fn test_acks_for_other_nonces_are_ignored() {
+    let mut pmtud = Plpmtud::new(1200, 1500, true);
+    let now = Instant::now();
+    assert!(pmtud.on_timer(now, RTO, 7) == Some(1200));
+    assert!(!pmtud.on_ack(8, now));
+    assert!(pmtud.on_timer(now, RTO, 9).is_none());
+    assert!(pmtud.deadline() == Some(now + RTO));
+}
+
+#[test]
+fn test_black_hole_falls_back_to_base() {
+    let mut pmtud = Plpmtud::new(1200, 1500, true);
+    let now = Instant::now();
+    probe(&mut pmtud, 1500, now);
+
+    // losses of small packets or interrupted by acks aren't a black hole
+    assert!(!pmtud.on_data_lost(1000, now));
+    assert!(!pmtud.on_data_lost(1500, now));
+    pmtud.on_data_acked();
+    for _ in 1..MAX_PROBES {
+        assert!(!pmtud.on_data_lost(1500, now));
+    }
+    assert!(pmtud.on_data_lost(1500, now));
+    assert!(pmtud.state() == PlpmtudState::Base);
+    assert!(pmtud.plpmtu() == 1200);
+
+    // the path recovers at a smaller size
+    probe(&mut pmtud, 1300, now);
+    assert!(pmtud.plpmtu() > 1200 && pmtud.plpmtu() <= 1300);
+}
+
+#[test]
+fn test_base_loss() {
+    let mut pmtud = Plpmtud::new(1200, 1500, true);
+    let mut now = Instant::now();
+    for nonce in 0..MAX_PROBES as u64 {
+        assert!(pmtud.on_timer(now, RTO, nonce) == Some(1200));
+        now += RTO;
+    }
+    // the base keeps being probed until a probe gets through
+    assert!(pmtud.on_timer(now, RTO, 7) == Some(1200));
+    assert!(pmtud.state() == PlpmtudState::Error);
+    assert!(pmtud.plpmtu() == 1200);
+
+    assert!(pmtud.on_ack(7, now));
+    assert!(pmtud.state() == PlpmtudState::Search);
+}
+
+#[test]
+fn test_raise_timer_restarts_the_search() {
+    let mut pmtud = Plpmtud::new(1200, 1500, true);
+    let now = Instant::now();
+    probe(&mut pmtud, 1400, now);
+    assert!(pmtud.state() == PlpmtudState::SearchComplete);
+
+    let later = pmtud.deadline().unwrap();
+    assert!(later >= now + PMTU_RAISE_TIMER);
+    probe(&mut pmtud, 1500, later);
+    assert!(pmtud.state() == PlpmtudState::SearchComplete);
+    assert!(pmtud.plpmtu() == 1500);
+}
+
+#[test]
+fn test_disabled() {
+    let mut pmtud = Plpmtud::new(1200, 9000, false);
+    assert!(pmtud.state() == PlpmtudState::Disabled);
+    assert!(pmtud.plpmtu() == 9000);
+    assert!(pmtud.deadline().is_none());
+    assert!(pmtud.on_timer(Instant::now(), RTO, 0).is_none());
+}
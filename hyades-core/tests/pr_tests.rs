@@ -0,0 +1,117 @@
+use hyades_core::chunk::{Data, SkippedMessages};
+use hyades_core::config::PrPolicy;
+use hyades_core::pr::{forward_tsn, i_forward_tsn, AbandonedCounters, AbandonedStats, Abandonment};
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[test]
+fn test_abandonment_policies() {
+    let now = Instant::now();
+
+    let reliable = Abandonment::new(PrPolicy::Reliable, now);
+    assert!(!reliable.is_expired(now + Duration::from_secs(3600)));
+    assert!(!reliable.is_exhausted(u32::MAX));
+
+    let timed = Abandonment::new(PrPolicy::Timed(Duration::from_millis(100)), now);
+    assert!(!timed.is_expired(now + Duration::from_millis(99)));
+    assert!(timed.is_expired(now + Duration::from_millis(100)));
+    assert!(!timed.is_exhausted(10));
+
+    let limited = Abandonment::new(PrPolicy::MaxRetransmissions(2), now);
+    assert!(!limited.is_exhausted(1));
+    assert!(limited.is_exhausted(2));
+    assert!(!limited.is_expired(now + Duration::from_secs(3600)));
+
+    let prioritized = Abandonment::new(PrPolicy::Priority(3), now);
+    assert!(prioritized.priority() == Some(3));
+    assert!(limited.priority().is_none());
+    assert!(!prioritized.is_expired(now + Duration::from_secs(3600)));
+    assert!(!prioritized.is_exhausted(u32::MAX));
+}
+
+#[test]
+fn test_abandoned_stats_by_policy() {
+    let mut stats = AbandonedStats::default();
+    stats.record(PrPolicy::Timed(Duration::from_millis(100)), false, 100);
+    stats.record(PrPolicy::Timed(Duration::from_millis(100)), true, 50);
+    stats.record(PrPolicy::Priority(1), false, 10);
+    stats.record(PrPolicy::Priority(2), false, 20);
+    // reliable msgs are never abandoned
+    stats.record(PrPolicy::Reliable, true, 1);
+
+    assert!(
+        stats.timed
+            == AbandonedCounters {
+                unsent_msgs: 1,
+                unsent_bytes: 100,
+                sent_msgs: 1,
+                sent_bytes: 50,
+            }
+    );
+    assert!(stats.priority.unsent_msgs == 2 && stats.priority.unsent_bytes == 30);
+    assert!(stats.priority.sent_msgs == 0);
+    assert!(stats.max_retransmissions == AbandonedCounters::default());
+}
+
+#[test]
+fn test_forward_tsn_skips_abandoned_chunks_at_the_front() {
+    let chunks = [
+        (Data::new(10, 0, 4, 0, true, false, vec![1]), true),
+        (Data::new(11, 0, 4, 0, false, true, vec![1]), true),
+        (Data::new(12, 1, 7, 0, true, true, vec![1]), true),
+        (
+            Data::new(13, 2, 0, 0, true, true, vec![1]).unordered(true),
+            true,
+        ),
+        (Data::new(14, 0, 5, 0, true, true, vec![1]), false),
+        // abandoned chunks after one that isn't can't be skipped yet
+        (Data::new(15, 1, 8, 0, true, true, vec![1]), true),
+    ];
+    let fwd = forward_tsn(chunks.iter().map(|(chunk, abandoned)| (chunk, *abandoned))).unwrap();
+    assert!(fwd.new_cumulative_tsn() == 13);
+    // unordered msgs have no seq no to skip
+    assert!(fwd.streams() == [(0, 4), (1, 7)]);
+
+    assert!(forward_tsn(
+        chunks[4..]
+            .iter()
+            .map(|(chunk, abandoned)| (chunk, *abandoned))
+    )
+    .is_none());
+}
+
+#[test]
+fn test_i_forward_tsn_lists_unordered_msgs() {
+    let chunks = [
+        (Data::new(10, 0, 0, 0, true, true, vec![1]), 70000, true),
+        (
+            Data::new(11, 0, 0, 0, true, true, vec![1]).unordered(true),
+            3,
+            true,
+        ),
+        (Data::new(12, 0, 0, 0, true, true, vec![1]), 70001, true),
+        (Data::new(13, 1, 0, 0, true, true, vec![1]), 0, false),
+    ];
+    let fwd = i_forward_tsn(
+        chunks
+            .iter()
+            .map(|(chunk, mid, abandoned)| (chunk, *mid, *abandoned)),
+    )
+    .unwrap();
+    assert!(fwd.new_cumulative_tsn() == 12);
+    assert!(
+        fwd.streams()
+            == [
+                SkippedMessages {
+                    stream_id: 0,
+                    unordered: false,
+                    message_id: 70001,
+                },
+                SkippedMessages {
+                    stream_id: 0,
+                    unordered: true,
+                    message_id: 3,
+                },
+            ]
+    );
+}
@@ -0,0 +1,133 @@
+use hyades_core::chunk::{Data, IData, SkippedMessages};
+use hyades_core::reassembly::{InterleavedReassemblyQueue, Reassembled, ReassemblyQueue};
+
+#[test]
+fn test_reassemble_in_order_fragments() {
+    let mut queue = ReassemblyQueue::new(1000);
+
+    assert!(queue.push(Data::new(1, 0, 5, 51, true, false, vec![1, 2])) == Reassembled::Pending);
+    assert!(queue.push(Data::new(2, 0, 5, 0, false, false, vec![3])) == Reassembled::Pending);
+    match queue.push(Data::new(3, 0, 5, 0, false, true, vec![4, 5, 6])) {
+        Reassembled::Complete(msg) => {
+            assert!(msg.stream_seq_no == 5);
+            assert!(msg.ppid == 51);
+            assert!(msg.data == vec![1, 2, 3, 4, 5, 6]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_reassemble_out_of_order_fragments() {
+    let mut queue = ReassemblyQueue::new(1000);
+
+    assert!(queue.push(Data::new(12, 0, 0, 0, false, true, vec![3])) == Reassembled::Pending);
+    assert!(queue.push(Data::new(10, 0, 0, 0, true, false, vec![1])) == Reassembled::Pending);
+    match queue.push(Data::new(11, 0, 0, 0, false, false, vec![2])) {
+        Reassembled::Complete(msg) => assert!(msg.data == vec![1, 2, 3]),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_discard_oversized_message() {
+    let mut queue = ReassemblyQueue::new(4);
+
+    assert!(queue.push(Data::new(1, 0, 0, 0, true, false, vec![0; 3])) == Reassembled::Pending);
+    assert!(
+        queue.push(Data::new(2, 0, 0, 0, false, false, vec![0; 3])) == Reassembled::Discarded(6)
+    );
+    // the rest of the msg is thrown away as it arrives
+    assert!(
+        queue.push(Data::new(3, 0, 0, 0, false, true, vec![0; 3])) == Reassembled::Discarded(3)
+    );
+    assert!(queue.is_empty());
+
+    match queue.push(Data::new(4, 0, 1, 0, true, true, vec![9])) {
+        Reassembled::Complete(msg) => assert!(msg.data == vec![9]),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_reassemble_interleaved_fragments() {
+    let mut queue = InterleavedReassemblyQueue::new(1000);
+    let fragment =
+        |tsn, mid, fsn, start, end, data| IData::new(tsn, 0, mid, fsn, 51, start, end, data);
+
+    // the fragments of msgs 1 and 2 on the same stream arrive interleaved
+    assert!(queue.push(fragment(1, 1, 0, true, false, vec![1])) == Reassembled::Pending);
+    assert!(queue.push(fragment(2, 2, 0, true, false, vec![4])) == Reassembled::Pending);
+    assert!(queue.push(fragment(4, 1, 2, false, true, vec![3])) == Reassembled::Pending);
+    match queue.push(fragment(3, 2, 1, false, true, vec![5])) {
+        Reassembled::Complete(msg) => {
+            assert!(msg.stream_seq_no == 2 && msg.ppid == 51);
+            assert!(msg.data == vec![4, 5]);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    match queue.push(fragment(5, 1, 1, false, false, vec![2])) {
+        Reassembled::Complete(msg) => assert!(msg.data == vec![1, 2, 3]),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(queue.is_empty());
+
+    // an ordered and an unordered msg can share a message id
+    assert!(queue.push(fragment(6, 3, 0, true, false, vec![1])) == Reassembled::Pending);
+    assert!(
+        queue.push(IData::new(7, 0, 3, 0, 0, true, true, vec![9]).unordered(true))
+            != Reassembled::Pending
+    );
+    assert!(queue.on_forward_tsn(6, &[]) == 1);
+    assert!(queue.is_empty());
+
+    // the message id is kept whole
+    match queue.push(fragment(8, 0x10001, 0, true, true, vec![1])) {
+        Reassembled::Complete(msg) => assert!(msg.stream_seq_no == 0x10001),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn test_discard_oversized_interleaved_message() {
+    let mut queue = InterleavedReassemblyQueue::new(4);
+
+    assert!(queue.push(IData::new(1, 0, 0, 0, 0, true, false, vec![0; 3])) == Reassembled::Pending);
+    assert!(
+        queue.push(IData::new(2, 0, 0, 1, 0, false, false, vec![0; 3]))
+            == Reassembled::Discarded(6)
+    );
+    assert!(
+        queue.push(IData::new(3, 0, 0, 2, 0, false, true, vec![0; 2])) == Reassembled::Discarded(2)
+    );
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn test_interleaved_forward_tsn() {
+    let mut queue = InterleavedReassemblyQueue::new(4);
+
+    // msg 0 is oversized and discarded, msg 1 is pending
+    assert!(queue.push(IData::new(1, 0, 0, 0, 0, true, false, vec![0; 3])) == Reassembled::Pending);
+    assert!(
+        queue.push(IData::new(3, 0, 0, 1, 0, false, false, vec![0; 3])) != Reassembled::Pending
+    );
+    assert!(queue.push(IData::new(2, 0, 1, 0, 0, true, false, vec![0; 2])) == Reassembled::Pending);
+
+    // neither is covered by the new cumulative TSN, so the rest of msg 0 is still discarded
+    assert!(queue.on_forward_tsn(1, &[]) == 0);
+    assert!(
+        queue.push(IData::new(4, 0, 0, 2, 0, false, true, vec![0; 2])) == Reassembled::Discarded(2)
+    );
+
+    // a skipped msg is dropped whatever its TSNs
+    let skipped = SkippedMessages {
+        stream_id: 0,
+        unordered: false,
+        message_id: 1,
+    };
+    assert!(queue.on_forward_tsn(1, &[skipped]) == 2);
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,101 @@
+use hyades_core::chunk::{ReconfigRequest, ReconfigResponse, ReconfigResult};
+use hyades_core::reconfig::{ReconfigReceiver, ReconfigSender, ReconfigVerdict};
+
+fn response(seq_no: u32, result: ReconfigResult) -> ReconfigResponse {
+    ReconfigResponse {
+        seq_no,
+        result,
+        next_tsns: None,
+    }
+}
+
+#[test]
+fn test_requests_are_numbered_and_matched() {
+    let mut sender = ReconfigSender::new(100);
+
+    let reconfig = sender
+        .send(vec![
+            ReconfigRequest::SsnTsnReset { seq_no: 0 },
+            ReconfigRequest::AddOutgoingStreams {
+                seq_no: 0,
+                num_streams: 2,
+            },
+        ])
+        .unwrap();
+    let seq_nos: Vec<u32> = reconfig.requests().iter().map(|r| r.seq_no()).collect();
+    assert!(seq_nos == [100, 101]);
+    assert!(sender
+        .send(vec![ReconfigRequest::SsnTsnReset { seq_no: 0 }])
+        .is_none());
+
+    // a response for another seq no is ignored
+    assert!(sender
+        .on_response(&response(99, ReconfigResult::SuccessPerformed))
+        .is_none());
+    // an in progress request stays outstanding
+    assert!(sender
+        .on_response(&response(100, ReconfigResult::InProgress))
+        .is_none());
+    assert!(sender.take_in_progress() && !sender.take_in_progress());
+    assert!(sender.outstanding().len() == 2);
+
+    assert!(sender
+        .on_response(&response(100, ReconfigResult::SuccessPerformed))
+        .is_some());
+    assert!(sender
+        .on_response(&response(101, ReconfigResult::Denied))
+        .is_some());
+    assert!(sender.outstanding().is_empty());
+    let results = sender.take_results();
+    assert!(results.len() == 2 && !results[1].1.result.is_success());
+
+    let reconfig = sender
+        .send(vec![ReconfigRequest::IncomingSsnReset {
+            seq_no: 0,
+            streams: vec![],
+        }])
+        .unwrap();
+    assert!(reconfig.requests()[0].seq_no() == 102);
+}
+
+#[test]
+fn test_requests_are_processed_in_sequence() {
+    let mut receiver = ReconfigReceiver::new(10);
+    let request = |seq_no| ReconfigRequest::SsnTsnReset { seq_no };
+
+    assert!(matches!(
+        receiver.on_request(&request(11)),
+        ReconfigVerdict::BadSeqNo
+    ));
+    assert!(matches!(
+        receiver.on_request(&request(10)),
+        ReconfigVerdict::New
+    ));
+
+    // a request that is in progress is processed again
+    receiver.on_processed(response(10, ReconfigResult::InProgress));
+    assert!(matches!(
+        receiver.on_request(&request(10)),
+        ReconfigVerdict::New
+    ));
+
+    receiver.on_processed(response(10, ReconfigResult::SuccessPerformed));
+    receiver.on_processed(response(11, ReconfigResult::Denied));
+    assert!(receiver.last_seq_no() == 11);
+    // the responses to the last two requests are sent again
+    match receiver.on_request(&request(10)) {
+        ReconfigVerdict::Retransmitted(response) => {
+            assert!(response.result == ReconfigResult::SuccessPerformed)
+        }
+        verdict => panic!("unexpected {:?}", verdict),
+    }
+    assert!(matches!(
+        receiver.on_request(&request(11)),
+        ReconfigVerdict::Retransmitted(_)
+    ));
+    receiver.on_processed(response(12, ReconfigResult::SuccessNothingToDo));
+    assert!(matches!(
+        receiver.on_request(&request(10)),
+        ReconfigVerdict::BadSeqNo
+    ));
+}
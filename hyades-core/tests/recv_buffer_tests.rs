@@ -0,0 +1,124 @@
+use hyades_core::recv_buffer::{DataVerdict, RecvBuffer};
+
+#[test]
+fn test_cumulative_tsn_and_gaps() {
+    let mut buffer = RecvBuffer::new(10000, 1500);
+    buffer.set_initial_tsn(100);
+
+    assert!(buffer.on_data(100, 10) == DataVerdict::Accepted);
+    assert!(buffer.on_data(102, 10) == DataVerdict::Accepted);
+    assert!(buffer.on_data(103, 10) == DataVerdict::Accepted);
+    assert!(buffer.on_data(102, 10) == DataVerdict::Duplicate);
+
+    let sack = buffer.sack();
+    assert!(sack.cumulative_tsn_ack == 100);
+    assert!(sack.gap_ack_blocks() == [(2, 3)]);
+    assert!(sack.dup_tsns() == [102]);
+
+    assert!(buffer.on_data(101, 10) == DataVerdict::Accepted);
+    let sack = buffer.sack();
+    assert!(sack.cumulative_tsn_ack == 103);
+    assert!(sack.gap_ack_blocks().is_empty());
+    assert!(sack.dup_tsns().is_empty());
+}
+
+#[test]
+fn test_data_beyond_window_is_dropped_unless_filling_a_hole() {
+    let mut buffer = RecvBuffer::new(100, 1500);
+    buffer.set_initial_tsn(u32::MAX);
+
+    assert!(buffer.on_data(u32::MAX, 40) == DataVerdict::Accepted);
+    assert!(buffer.on_data(1, 60) == DataVerdict::Accepted);
+    assert!(buffer.sack().a_rwnd == 0);
+
+    assert!(buffer.on_data(2, 10) == DataVerdict::Dropped);
+    assert!(buffer.on_data(0, 10) == DataVerdict::Accepted);
+    assert!(buffer.cumulative_tsn() == 1);
+}
+
+#[test]
+fn test_data_too_far_ahead_is_dropped() {
+    let mut buffer = RecvBuffer::new(1000000, 1500);
+    buffer.set_initial_tsn(u32::MAX - 10);
+
+    // the last TSN a 16 bit gap ack block offset can reach
+    let cum = buffer.cumulative_tsn();
+    assert!(buffer.on_data(cum.wrapping_add(65535), 10) == DataVerdict::Accepted);
+    assert!(buffer.on_data(cum.wrapping_add(65536), 10) == DataVerdict::Dropped);
+    assert!(buffer.on_data(cum.wrapping_add(65534), 10) == DataVerdict::Accepted);
+
+    let sack = buffer.sack();
+    assert!(sack.cumulative_tsn_ack == cum);
+    assert!(sack.gap_ack_blocks() == [(65534, 65535)]);
+}
+
+#[test]
+fn test_silly_window_avoidance() {
+    let mut buffer = RecvBuffer::new(4000, 1500);
+    buffer.set_initial_tsn(1);
+
+    assert!(buffer.on_data(1, 4000) == DataVerdict::Accepted);
+    assert!(buffer.a_rwnd() == 0);
+
+    // the window isn't reopened until it can grow by at least an mtu
+    buffer.on_delivered(1000);
+    assert!(buffer.a_rwnd() == 0);
+    buffer.on_delivered(500);
+    assert!(buffer.a_rwnd() == 1500);
+}
+
+#[test]
+fn test_forward_tsn_moves_cumulative_tsn() {
+    let mut buffer = RecvBuffer::new(10000, 1500);
+    buffer.set_initial_tsn(100);
+
+    assert!(buffer.on_data(100, 10) == DataVerdict::Accepted);
+    assert!(buffer.on_data(103, 10) == DataVerdict::Accepted);
+    assert!(buffer.on_data(105, 10) == DataVerdict::Accepted);
+
+    // 101 and 102 were abandoned and 103 was already recvd
+    assert!(buffer.on_forward_tsn(102));
+    assert!(buffer.cumulative_tsn() == 103);
+    assert!(buffer.sack().gap_ack_blocks() == [(2, 2)]);
+    assert!(!buffer.on_forward_tsn(101));
+}
+
+#[test]
+fn test_delivered_tsns_are_non_renegable() {
+    let mut buffer = RecvBuffer::new(10000, 1500);
+    buffer.set_initial_tsn(100);
+
+    for tsn in [100, 102, 103, 105] {
+        assert!(buffer.on_data(tsn, 10) == DataVerdict::Accepted);
+    }
+    // TSNs up to the cumulative TSN are never reported in gap ack blocks
+    buffer.on_msg_delivered(&[100, 103, 105]);
+
+    let sack = buffer.nr_sack();
+    assert!(sack.cumulative_tsn_ack() == 100);
+    assert!(sack.gap_ack_blocks() == [(2, 2)]);
+    assert!(sack.nr_gap_ack_blocks() == [(3, 3), (5, 5)]);
+
+    assert!(buffer.on_data(101, 10) == DataVerdict::Accepted);
+    let sack = buffer.nr_sack();
+    assert!(sack.cumulative_tsn_ack() == 103);
+    assert!(sack.gap_ack_blocks().is_empty());
+    assert!(sack.nr_gap_ack_blocks() == [(2, 2)]);
+}
+
+#[test]
+fn test_nr_sack_blocks_at_the_offset_limit() {
+    let mut buffer = RecvBuffer::new(1000000, 1500);
+    buffer.set_initial_tsn(100);
+    let cum = buffer.cumulative_tsn();
+
+    assert!(buffer.on_data(cum + 65535, 10) == DataVerdict::Accepted);
+    assert!(buffer.on_data(cum + 65536, 10) == DataVerdict::Dropped);
+    assert!(buffer.on_data(cum + 65534, 10) == DataVerdict::Accepted);
+    buffer.on_msg_delivered(&[cum + 65535, cum + 65536]);
+
+    let sack = buffer.nr_sack();
+    assert!(sack.cumulative_tsn_ack() == cum);
+    assert!(sack.gap_ack_blocks() == [(65534, 65534)]);
+    assert!(sack.nr_gap_ack_blocks() == [(65535, 65535)]);
+}
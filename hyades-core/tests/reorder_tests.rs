@@ -0,0 +1,121 @@
+use hyades_core::reassembly::Message;
+use hyades_core::reorder::ReorderQueue;
+
+fn msg(stream_id: u16, stream_seq_no: u32) -> Message {
+    Message {
+        stream_id,
+        stream_seq_no,
+        unordered: false,
+        ppid: 0,
+        data: vec![stream_seq_no as u8],
+        tsns: vec![],
+    }
+}
+
+#[test]
+fn test_msgs_are_delivered_in_order_per_stream() {
+    let mut queue = ReorderQueue::new(2);
+
+    assert!(queue.push(msg(0, 1)).is_empty());
+    assert!(queue.push(msg(0, 2)).is_empty());
+    // a gap on stream 0 doesn't hold back stream 1
+    assert!(queue.push(msg(1, 0)) == vec![msg(1, 0)]);
+
+    assert!(queue.push(msg(0, 0)) == vec![msg(0, 0), msg(0, 1), msg(0, 2)]);
+    assert!(queue.push(msg(0, 3)) == vec![msg(0, 3)]);
+}
+
+#[test]
+fn test_unordered_msgs_skip_the_queue() {
+    let mut queue = ReorderQueue::new(1);
+
+    assert!(queue.push(msg(0, 1)).is_empty());
+    let unordered = Message {
+        unordered: true,
+        ..msg(0, 0)
+    };
+    assert!(queue.push(unordered.clone()) == vec![unordered]);
+    // the unordered msg didn't use up stream seq no 0
+    assert!(queue.push(msg(0, 0)) == vec![msg(0, 0), msg(0, 1)]);
+}
+
+#[test]
+fn test_invalid_streams() {
+    let queue = ReorderQueue::new(2);
+
+    assert!(queue.num_streams() == 2);
+    assert!(queue.is_valid_stream(1));
+    assert!(!queue.is_valid_stream(2));
+}
+
+#[test]
+fn test_skipped_msgs_unblock_the_stream() {
+    let mut queue = ReorderQueue::new(1);
+
+    assert!(queue.push(msg(0, 1)).is_empty());
+    assert!(queue.push(msg(0, 3)).is_empty());
+    // msgs 0 and 2 were abandoned by the peer
+    assert!(queue.skip(0, 2) == vec![msg(0, 1), msg(0, 3)]);
+    // skipping msgs that were already delivered changes nothing
+    assert!(queue.skip(0, 1).is_empty());
+    assert!(queue.push(msg(0, 4)) == vec![msg(0, 4)]);
+}
+
+#[test]
+fn test_reset_and_added_streams() {
+    let mut queue = ReorderQueue::new(2);
+    assert!(queue.push(msg(0, 0)) == vec![msg(0, 0)]);
+    assert!(queue.push(msg(1, 0)) == vec![msg(1, 0)]);
+    assert!(queue.push(msg(1, 2)).is_empty());
+
+    // a reset stream starts over from seq no 0 and drops what it held back
+    queue.reset(&[1]);
+    assert!(queue.push(msg(1, 0)) == vec![msg(1, 0)]);
+    assert!(queue.push(msg(0, 1)) == vec![msg(0, 1)]);
+    queue.reset(&[]);
+    assert!(queue.push(msg(0, 0)) == vec![msg(0, 0)]);
+
+    assert!(!queue.is_valid_stream(2));
+    queue.add_streams(2);
+    assert!(queue.num_streams() == 4);
+    assert!(queue.push(msg(3, 0)) == vec![msg(3, 0)]);
+}
+
+#[test]
+fn test_stale_msgs_are_dropped() {
+    let mut queue = ReorderQueue::new(1);
+
+    assert!(queue.push(msg(0, 0)) == vec![msg(0, 0)]);
+    // a retransmitted msg that was delivered already
+    assert!(queue.push(msg(0, 0)).is_empty());
+
+    // it isn't held back until the seq nos wrap around
+    for ssn in 1..u16::MAX as u32 {
+        assert!(queue.push(msg(0, ssn)) == vec![msg(0, ssn)]);
+    }
+    assert!(queue.push(msg(0, u16::MAX as u32)) == vec![msg(0, u16::MAX as u32)]);
+    assert!(queue.push(msg(0, 0)) == vec![msg(0, 0)]);
+}
+
+#[test]
+fn test_interleaved_msgs_are_ordered_by_32_bit_message_id() {
+    let mut queue = ReorderQueue::new(1);
+    queue.set_interleaving(true);
+    assert!(queue.skip(0, u16::MAX as u32 - 1).is_empty());
+
+    // message ids don't wrap around after 2^16 - 1
+    assert!(queue.push(msg(0, 0x10000)).is_empty());
+    // message id 0 was skipped, it isn't the same as 0x10000
+    assert!(queue.push(msg(0, 0)).is_empty());
+    assert!(queue.push(msg(0, 0xffff)) == vec![msg(0, 0xffff), msg(0, 0x10000)]);
+    assert!(queue.skip(0, 0x10002).is_empty());
+    assert!(queue.push(msg(0, 0x10003)) == vec![msg(0, 0x10003)]);
+
+    // they wrap around after 2^32 - 1
+    let mut queue = ReorderQueue::new(1);
+    queue.set_interleaving(true);
+    assert!(queue.skip(0, 0x7fff_fffe).is_empty());
+    assert!(queue.skip(0, u32::MAX - 1).is_empty());
+    assert!(queue.push(msg(0, 0)).is_empty());
+    assert!(queue.push(msg(0, u32::MAX)) == vec![msg(0, u32::MAX), msg(0, 0)]);
+}
@@ -0,0 +1,49 @@
+use hyades_core::rto::Rto;
+use std::time::Duration;
+
+fn rto() -> Rto {
+    Rto::new(
+        Duration::from_secs(3),
+        Duration::from_secs(1),
+        Duration::from_secs(60),
+    )
+}
+
+#[test]
+fn test_first_measurement() {
+    let mut rto = rto();
+    assert!(rto.srtt().is_none());
+    assert!(rto.rto() == Duration::from_secs(3));
+
+    rto.on_measurement(Duration::from_millis(2000));
+    assert!(rto.srtt() == Some(Duration::from_millis(2000)));
+    assert!(rto.rttvar() == Duration::from_millis(1000));
+    assert!(rto.rto() == Duration::from_millis(6000));
+}
+
+#[test]
+fn test_subsequent_measurement() {
+    let mut rto = rto();
+    rto.on_measurement(Duration::from_millis(2000));
+    rto.on_measurement(Duration::from_millis(1000));
+    // rttvar = 3/4 * 1000 + 1/4 * 1000, srtt = 7/8 * 2000 + 1/8 * 1000
+    assert!(rto.rttvar() == Duration::from_millis(1000));
+    assert!(rto.srtt() == Some(Duration::from_millis(1875)));
+    assert!(rto.rto() == Duration::from_millis(5875));
+}
+
+#[test]
+fn test_rto_is_clamped() {
+    let mut rto = rto();
+    rto.on_measurement(Duration::from_millis(10));
+    assert!(rto.rto() == Duration::from_secs(1));
+
+    for _ in 0..10 {
+        rto.backoff();
+    }
+    assert!(rto.rto() == Duration::from_secs(60));
+
+    // a valid measurement after backoff resets the rto
+    rto.on_measurement(Duration::from_millis(10));
+    assert!(rto.rto() == Duration::from_secs(1));
+}
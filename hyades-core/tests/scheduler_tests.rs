@@ -0,0 +1,76 @@
+use hyades_core::scheduler::{Scheduler, StreamScheduler};
+
+// sends `count` single chunk msgs of `bytes` bytes on the streams picked from `streams`
+fn schedule(scheduler: &mut Scheduler, streams: &[u16], count: usize, bytes: usize) -> Vec<u16> {
+    (0..count)
+        .map(|_| {
+            let stream_id = scheduler.pick(streams).unwrap();
+            scheduler.on_sent(stream_id, bytes, true);
+            stream_id
+        })
+        .collect()
+}
+
+#[test]
+fn test_fcfs_and_round_robin() {
+    let mut fcfs = Scheduler::new(StreamScheduler::Fcfs, 3);
+    assert!(fcfs.pick(&[]).is_none());
+    assert!(schedule(&mut fcfs, &[2, 0], 2, 100) == [2, 2]);
+
+    let mut rr = Scheduler::new(StreamScheduler::RoundRobin, 3);
+    assert!(schedule(&mut rr, &[2, 0, 1], 4, 100) == [0, 1, 2, 0]);
+
+    // the fragments of a msg are sent before the next stream gets its turn
+    rr.on_sent(1, 100, false);
+    assert!(rr.pick(&[0, 1, 2]) == Some(1));
+    rr.on_sent(1, 100, true);
+    assert!(rr.pick(&[0, 1, 2]) == Some(2));
+}
+
+#[test]
+fn test_round_robin_per_packet_interleaves_msgs() {
+    let mut rr = Scheduler::new(StreamScheduler::RoundRobinPerPacket, 2);
+    rr.on_sent(0, 100, false);
+    // without I-DATA the msg has to be finished first
+    assert!(rr.pick(&[0, 1]) == Some(0));
+
+    rr.set_interleaving(true);
+    assert!(rr.pick(&[0, 1]) == Some(1));
+}
+
+#[test]
+fn test_priority() {
+    let mut scheduler = Scheduler::new(StreamScheduler::Priority, 4);
+    scheduler.set_priority(1, 5).unwrap();
+    scheduler.set_priority(2, 1).unwrap();
+    scheduler.set_priority(3, 1).unwrap();
+    assert!(scheduler.set_priority(4, 1).is_err());
+
+    // streams of the same priority take turns
+    assert!(schedule(&mut scheduler, &[1, 2, 3], 3, 100) == [2, 3, 2]);
+    assert!(scheduler.pick(&[1]) == Some(1));
+}
+
+#[test]
+fn test_fair_queueing() {
+    // stream 0 sends chunks twice as large as stream 1
+    let mut fair = Scheduler::new(StreamScheduler::FairBandwidth, 2);
+    let mut sent = [0; 2];
+    for _ in 0..30 {
+        let stream_id = fair.pick(&[0, 1]).unwrap();
+        let bytes = if stream_id == 0 { 200 } else { 100 };
+        fair.on_sent(stream_id, bytes, true);
+        sent[stream_id as usize] += bytes;
+    }
+    assert!(sent == [2000, 2000]);
+
+    let mut wfq = Scheduler::new(StreamScheduler::WeightedFairQueueing, 2);
+    wfq.set_weight(1, 3).unwrap();
+    let streams = schedule(&mut wfq, &[0, 1], 40, 100);
+    assert!(streams.iter().filter(|&&stream_id| stream_id == 1).count() == 30);
+
+    // a stream that was idle doesn't make up for the time it didn't send
+    wfq.add_streams(1);
+    let streams = schedule(&mut wfq, &[0, 2], 10, 100);
+    assert!(streams.iter().filter(|&&stream_id| stream_id == 2).count() == 5);
+}